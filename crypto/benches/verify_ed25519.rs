@@ -0,0 +1,55 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Compares ed25519 batch verification with verifying signatures one by one.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tezos_crypto_rs::batch::verify_ed25519_batch;
+use tezos_crypto_rs::hash::{Ed25519Signature, HashTrait, PublicKeyEd25519, SeedEd25519};
+use tezos_crypto_rs::PublicKeySignatureVerifier;
+
+fn signed(n: usize) -> Vec<(PublicKeyEd25519, Ed25519Signature, Vec<u8>)> {
+    (0..n)
+        .map(|i| {
+            let mut seed = [0; 32];
+            seed[..8].copy_from_slice(&i.to_le_bytes());
+            let (pk, sk) = SeedEd25519::try_from_bytes(&seed)
+                .unwrap()
+                .keypair()
+                .unwrap();
+            let msg = format!("endorsement {}", i).into_bytes();
+            let signature = sk.sign(&msg).unwrap();
+            (pk, signature, msg)
+        })
+        .collect()
+}
+
+fn bench_verify_ed25519(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify_ed25519");
+
+    for size in [1, 16, 64, 256] {
+        let signed = signed(size);
+        let batch: Vec<_> = signed
+            .iter()
+            .map(|(pk, signature, msg)| (pk, signature, msg.as_slice()))
+            .collect();
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("single", size), &batch, |b, batch| {
+            b.iter(|| {
+                for (pk, signature, msg) in batch {
+                    assert!(pk.verify_signature(signature, msg).unwrap());
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("batch", size), &batch, |b, batch| {
+            b.iter(|| verify_ed25519_batch(batch).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_verify_ed25519);
+criterion_main!(benches);
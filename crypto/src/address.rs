@@ -0,0 +1,128 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// Ported from octez: src/proto_alpha/lib_protocol/destination_repr.ml
+//
+// Copyright (c) 2021 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+use crate::base58::FromBase58CheckError;
+use crate::hash::{ContractKt1Hash, HashTrait, SmartRollupHash};
+use crate::public_key_hash::PublicKeyHash;
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+
+/// Address of any Tezos account, that can be the destination of an operation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Address {
+    /// Tz1, tz2, tz3 or tz4 - implicit account.
+    Implicit(PublicKeyHash),
+    /// KT1 - originated smart contract.
+    Originated(ContractKt1Hash),
+    /// Sr1 - smart rollup.
+    SmartRollup(SmartRollupHash),
+}
+
+impl Address {
+    pub fn from_base58_check(data: &str) -> Result<Self, FromBase58CheckError> {
+        if data.starts_with("tz") {
+            Ok(Address::Implicit(PublicKeyHash::from_base58_check(data)?))
+        } else if data.starts_with("KT1") {
+            Ok(Address::Originated(ContractKt1Hash::from_b58check(data)?))
+        } else if data.starts_with("sr1") {
+            Ok(Address::SmartRollup(SmartRollupHash::from_b58check(data)?))
+        } else {
+            Err(FromBase58CheckError::IncorrectBase58Prefix)
+        }
+    }
+
+    pub fn to_base58_check(&self) -> String {
+        match self {
+            Self::Implicit(h) => h.to_base58_check(),
+            Self::Originated(h) => h.to_b58check(),
+            Self::SmartRollup(h) => h.to_b58check(),
+        }
+    }
+}
+
+impl TryFrom<&str> for Address {
+    type Error = FromBase58CheckError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Address::from_base58_check(s)
+    }
+}
+
+impl ::core::str::FromStr for Address {
+    type Err = FromBase58CheckError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Address::from_base58_check(s)
+    }
+}
+
+impl ::core::fmt::Display for Address {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.to_base58_check())
+    }
+}
+
+macro_rules! from_h_for_address {
+    ($h:ident, $name:ident) => {
+        impl From<$h> for Address {
+            fn from(h: $h) -> Self {
+                Self::$name(h)
+            }
+        }
+    };
+}
+from_h_for_address!(PublicKeyHash, Implicit);
+from_h_for_address!(ContractKt1Hash, Originated);
+from_h_for_address!(SmartRollupHash, SmartRollup);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_b58_round_trip() {
+        for address in [
+            "tz1KqTpEZ7Yob7QbPE4Hy4Wo8fHG8LhKxZSx",
+            "tz2TRpDZTo2uWH1BuiQD58pmywTmD1kt6NWt",
+            "tz3V4z2s9854NWrWqX7ndmAUB558AdDB8XMw",
+            "tz4NPp9xHJMgoRQwE2iL66NnfeBcnEoskeuj",
+            "KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi",
+            "sr1UNDWPUYVeomgG15wn5jSw689EJ4RNnVQa",
+        ] {
+            let parsed = Address::from_base58_check(address).expect("valid address");
+            assert_eq!(parsed.to_base58_check(), address);
+            assert_eq!(parsed.to_string(), address);
+            assert_eq!(address.parse::<Address>().unwrap(), parsed);
+
+            let json = serde_json::to_string(&parsed).unwrap();
+            assert_eq!(json, format!("\"{}\"", address));
+            assert_eq!(serde_json::from_str::<Address>(&json).unwrap(), parsed);
+        }
+    }
+
+    #[test]
+    fn test_variants() {
+        assert!(matches!(
+            Address::from_base58_check("tz3V4z2s9854NWrWqX7ndmAUB558AdDB8XMw"),
+            Ok(Address::Implicit(PublicKeyHash::P256(_)))
+        ));
+        assert!(matches!(
+            Address::from_base58_check("KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi"),
+            Ok(Address::Originated(_))
+        ));
+        assert!(matches!(
+            Address::from_base58_check("sr1UNDWPUYVeomgG15wn5jSw689EJ4RNnVQa"),
+            Ok(Address::SmartRollup(_))
+        ));
+        assert!(matches!(
+            Address::from_base58_check("edpkuBknW28nW72KG6RoHtYW7p12T6GKc7nAbwYX5m8Wd9sDVC9yav"),
+            Err(FromBase58CheckError::IncorrectBase58Prefix)
+        ));
+    }
+}
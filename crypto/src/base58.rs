@@ -2,6 +2,8 @@
 // SPDX-FileCopyrightText: 2023 TriliTech <contact@trili.tech>
 // SPDX-License-Identifier: MIT
 
+use alloc::string::String;
+use alloc::vec::Vec;
 use cryptoxide::hashing::sha256;
 use thiserror::Error;
 
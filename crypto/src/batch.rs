@@ -0,0 +1,516 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Batch verification of signatures.
+
+#[cfg(any(feature = "bls", feature = "bls-rust"))]
+use crate::hash::{BlsSignature, PublicKeyBls};
+use crate::hash::{Ed25519Signature, PublicKeyEd25519};
+use crate::public_key::PublicKey;
+use crate::signature::Signature;
+use crate::{CryptoError, PublicKeySignatureVerifier};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use thiserror::Error;
+
+/// Failure of a batch verification.
+#[derive(Debug, Error)]
+#[error("invalid signature at index {index}: {error}")]
+pub struct BatchVerificationError {
+    /// Index of the first offending entry of the batch.
+    pub index: usize,
+    pub error: CryptoError,
+}
+
+/// Verify a batch of ed25519 signatures, with the same outcome as calling
+/// [PublicKeyEd25519::verify_signature] on each `(public key, signature, message)`
+/// entry.
+///
+/// All signatures are checked at once using ed25519 batch verification, except
+/// for entries whose public key or `R` component is not of prime order, which
+/// are verified one by one. Only when the batch fails are its entries verified
+/// one by one, to find the first offending one.
+pub fn verify_ed25519_batch(
+    batch: &[(&PublicKeyEd25519, &Ed25519Signature, &[u8])],
+) -> Result<(), BatchVerificationError> {
+    let mut indices = Vec::with_capacity(batch.len());
+    let mut payloads = Vec::with_capacity(batch.len());
+    let mut signatures = Vec::with_capacity(batch.len());
+    let mut keys = Vec::with_capacity(batch.len());
+
+    // First offending entry found while preparing the batch: the entries after
+    // it need not be checked.
+    let mut failure = None;
+
+    for (index, (pk, signature, msg)) in batch.iter().enumerate() {
+        let error = |error| BatchVerificationError { index, error };
+
+        let signature = match signature.as_ref().try_into() {
+            Ok(bytes) => ed25519_dalek::Signature::from_bytes(bytes),
+            Err(_) => {
+                failure = Some(error(CryptoError::InvalidSignature));
+                break;
+            }
+        };
+        let key = match ed25519_dalek::VerifyingKey::try_from(*pk) {
+            Ok(key) => key,
+            Err(_) => {
+                failure = Some(error(CryptoError::InvalidPublicKey));
+                break;
+            }
+        };
+
+        // Batch verification checks the equation up to the torsion components
+        // of keys and `R`, which strict verification rejects: entries with any
+        // such component are left to strict verification.
+        let prime_order = |bytes: [u8; 32]| {
+            CompressedEdwardsY(bytes)
+                .decompress()
+                .is_some_and(|point| !point.is_small_order() && point.is_torsion_free())
+        };
+        if key.is_weak() || !prime_order(*signature.r_bytes()) || !prime_order(key.to_bytes()) {
+            match verify_ed25519_one(index, batch[index]) {
+                Ok(()) => continue,
+                Err(error) => {
+                    failure = Some(error);
+                    break;
+                }
+            }
+        }
+
+        indices.push(index);
+        payloads.push(crate::blake2b::digest_256(msg));
+        signatures.push(signature);
+        keys.push(key);
+    }
+
+    let messages: Vec<&[u8]> = payloads.iter().map(Vec::as_slice).collect();
+    if ed25519_dalek::verify_batch(&messages, &signatures, &keys).is_err() {
+        // All batched entries come before `failure`.
+        indices
+            .into_iter()
+            .try_for_each(|index| verify_ed25519_one(index, batch[index]))?;
+    }
+
+    failure.map_or(Ok(()), Err)
+}
+
+fn verify_ed25519_one(
+    index: usize,
+    (pk, signature, msg): (&PublicKeyEd25519, &Ed25519Signature, &[u8]),
+) -> Result<(), BatchVerificationError> {
+    match pk.verify_signature(signature, msg) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(BatchVerificationError {
+            index,
+            error: CryptoError::InvalidSignature,
+        }),
+        Err(error) => Err(BatchVerificationError { index, error }),
+    }
+}
+
+/// Verifier of signatures of any curve, in batch.
+///
+/// Entries are grouped by curve: ed25519 signatures are checked using ed25519
+/// batch verification, and BLS signatures using [BlsSignature::batch_verify].
+/// Other entries, and the entries of a group that fails as a whole, are
+/// verified one by one.
+#[derive(Debug, Default)]
+pub struct BatchVerifier<'a> {
+    entries: Vec<(&'a PublicKey, &'a Signature, &'a [u8])>,
+}
+
+impl<'a> BatchVerifier<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Add the `signature` of `msg` by `public_key` to the batch.
+    pub fn add(&mut self, public_key: &'a PublicKey, signature: &'a Signature, msg: &'a [u8]) {
+        self.entries.push((public_key, signature, msg));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Verify all entries of the batch.
+    ///
+    /// Returns, in the order the entries were added, the same outcome as calling
+    /// [PublicKey::verify_signature] on each entry.
+    pub fn verify(&self) -> Vec<Result<(), CryptoError>> {
+        let mut results: Vec<Option<Result<(), CryptoError>>> =
+            self.entries.iter().map(|_| None).collect();
+
+        let mut ed25519: Vec<(usize, &PublicKeyEd25519, Cow<Ed25519Signature>, &[u8])> = Vec::new();
+        #[cfg(any(feature = "bls", feature = "bls-rust"))]
+        let mut bls: Vec<(usize, &PublicKeyBls, &BlsSignature, &[u8])> = Vec::new();
+
+        for (index, (public_key, signature, msg)) in self.entries.iter().enumerate() {
+            match (public_key, signature) {
+                (PublicKey::Ed25519(pk), Signature::Ed25519(sig)) => {
+                    ed25519.push((index, pk, Cow::Borrowed(sig), msg))
+                }
+                (PublicKey::Ed25519(pk), Signature::Unknown(sig)) => {
+                    ed25519.push((index, pk, Cow::Owned(sig.clone().into()), msg))
+                }
+                #[cfg(any(feature = "bls", feature = "bls-rust"))]
+                (PublicKey::Bls(pk), Signature::Bls(sig)) => bls.push((index, pk, sig, msg)),
+                _ => (),
+            }
+        }
+
+        // Each failure of the batch verification identifies an invalid entry,
+        // and the entries before it as valid: the rest is verified again.
+        let ed25519_batch: Vec<_> = ed25519
+            .iter()
+            .map(|(_, pk, sig, msg)| (*pk, sig.as_ref(), *msg))
+            .collect();
+        let mut start = 0;
+        while start < ed25519_batch.len() {
+            let end = match verify_ed25519_batch(&ed25519_batch[start..]) {
+                Ok(()) => ed25519_batch.len(),
+                Err(BatchVerificationError { index, error }) => {
+                    results[ed25519[start + index].0] = Some(Err(error));
+                    start + index
+                }
+            };
+            ed25519[start..end]
+                .iter()
+                .for_each(|(index, ..)| results[*index] = Some(Ok(())));
+            start = end + 1;
+        }
+
+        #[cfg(any(feature = "bls", feature = "bls-rust"))]
+        {
+            let bls_batch: Vec<_> = bls
+                .iter()
+                .map(|(_, pk, sig, msg)| (*sig, (*msg, *pk)))
+                .collect();
+            if !bls_batch.is_empty() && matches!(BlsSignature::batch_verify(&bls_batch), Ok(true)) {
+                bls.iter()
+                    .for_each(|(index, ..)| results[*index] = Some(Ok(())));
+            }
+        }
+
+        self.entries
+            .iter()
+            .zip(results)
+            .map(|((public_key, signature, msg), result)| {
+                result.unwrap_or_else(|| match public_key.verify_signature(signature, msg) {
+                    Ok(true) => Ok(()),
+                    Ok(false) => Err(CryptoError::InvalidSignature),
+                    Err(error) => Err(error),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{HashTrait, SeedEd25519};
+
+    fn keys(n: u8) -> Vec<(PublicKeyEd25519, crate::hash::SecretKeyEd25519)> {
+        (0..n)
+            .map(|i| {
+                SeedEd25519::try_from_bytes(&[i; 32])
+                    .unwrap()
+                    .keypair()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn signed(n: u8) -> Vec<(PublicKeyEd25519, Ed25519Signature, Vec<u8>)> {
+        keys(n)
+            .into_iter()
+            .map(|(pk, sk)| {
+                let msg = format!("message signed by {}", pk.to_b58check()).into_bytes();
+                let signature = sk.sign(&msg).unwrap();
+                (pk, signature, msg)
+            })
+            .collect()
+    }
+
+    // Secret scalar of an ed25519 key.
+    fn scalar(sk: &crate::hash::SecretKeyEd25519) -> curve25519_dalek::scalar::Scalar {
+        let mut expanded: [u8; 32] = cryptoxide::hashing::sha512(&sk.0)[..32].try_into().unwrap();
+        expanded[0] &= 248;
+        expanded[31] &= 127;
+        expanded[31] |= 64;
+        curve25519_dalek::scalar::Scalar::from_bytes_mod_order(expanded)
+    }
+
+    // Signature of `msg` whose `R` has a component of order 2: R = rB + T and
+    // s = r + H(R || A || M) * a. Strict verification rejects it, while the
+    // batch equation holds whenever its random coefficient for `R` is even.
+    fn mixed_order_signature(
+        pk: &PublicKeyEd25519,
+        sk: &crate::hash::SecretKeyEd25519,
+        msg: &[u8],
+    ) -> Ed25519Signature {
+        use cryptoxide::hashing::sha512;
+        use curve25519_dalek::edwards::EdwardsPoint;
+        use curve25519_dalek::scalar::Scalar;
+
+        // (0, -1), the point of order 2.
+        let mut t = [0xff; 32];
+        t[0] = 0xec;
+        t[31] = 0x7f;
+        let t = CompressedEdwardsY(t).decompress().unwrap();
+
+        let payload = crate::blake2b::digest_256(msg);
+        let r = Scalar::from_bytes_mod_order_wide(&sha512(&[&sk.0[..], msg].concat()));
+        let big_r = (EdwardsPoint::mul_base(&r) + t).compress().to_bytes();
+        let k = Scalar::from_bytes_mod_order_wide(&sha512(&[&big_r[..], &pk.0, &payload].concat()));
+        Ed25519Signature([big_r, (r + k * scalar(sk)).to_bytes()].concat())
+    }
+
+    fn verify(batch: &[(PublicKeyEd25519, Ed25519Signature, Vec<u8>)]) -> Result<(), usize> {
+        let batch: Vec<_> = batch
+            .iter()
+            .map(|(pk, signature, msg)| (pk, signature, msg.as_slice()))
+            .collect();
+        verify_ed25519_batch(&batch).map_err(|e| e.index)
+    }
+
+    #[test]
+    fn test_valid_batch() {
+        assert_eq!(verify(&[]), Ok(()));
+        assert_eq!(verify(&signed(1)), Ok(()));
+        assert_eq!(verify(&signed(16)), Ok(()));
+    }
+
+    #[test]
+    fn test_offending_index() {
+        let mut batch = signed(8);
+        batch[5].2 = b"other message".to_vec();
+        assert_eq!(verify(&batch), Err(5));
+
+        let mut batch = signed(8);
+        batch[2].1 = batch[6].1.clone();
+        assert_eq!(verify(&batch), Err(2));
+
+        let mut batch = signed(8);
+        batch[3].1 = Ed25519Signature::try_from_bytes(&[0xff; 64]).unwrap();
+        assert_eq!(verify(&batch), Err(3));
+
+        let mut batch = signed(8);
+        batch[7].0 = PublicKeyEd25519(vec![0; 31]);
+        assert!(matches!(
+            verify_ed25519_batch(&[(&batch[7].0, &batch[7].1, &batch[7].2)]),
+            Err(BatchVerificationError {
+                index: 0,
+                error: CryptoError::InvalidPublicKey
+            })
+        ));
+    }
+
+    #[test]
+    fn test_small_order_rejected() {
+        use cryptoxide::hashing::sha512;
+        use curve25519_dalek::scalar::Scalar;
+
+        let (pk, sk) = &keys(1)[0];
+        let msg = b"small order R";
+        let payload = crate::blake2b::digest_256(msg);
+
+        // Signature with `R` the identity, valid for non-strict verification:
+        // s = H(R || A || M) * a
+        let mut r = [0; 32];
+        r[0] = 1;
+        let a = scalar(sk);
+        let k = Scalar::from_bytes_mod_order_wide(&sha512(&[&r[..], &pk.0, &payload].concat()));
+        let signature = Ed25519Signature([r, (k * a).to_bytes()].concat());
+        assert!(ed25519_dalek::verify_batch(
+            &[&payload],
+            &[ed25519_dalek::Signature::from_slice(&signature.0).unwrap()],
+            &[ed25519_dalek::VerifyingKey::try_from(pk).unwrap()],
+        )
+        .is_ok());
+
+        let batch = signed(4);
+        let mut batch: Vec<_> = batch
+            .iter()
+            .map(|(pk, signature, msg)| (pk, signature, msg.as_slice()))
+            .collect();
+        batch.insert(1, (pk, &signature, msg));
+
+        assert!(matches!(
+            verify_ed25519_batch(&batch),
+            Err(BatchVerificationError { index: 1, .. })
+        ));
+
+        // Invalid signatures after and before the small order entry.
+        let other = signed(2);
+        let mut after = batch.clone();
+        after[3].1 = &other[0].1;
+        assert!(matches!(
+            verify_ed25519_batch(&after),
+            Err(BatchVerificationError { index: 1, .. })
+        ));
+        let mut before = batch.clone();
+        before[0].1 = &other[1].1;
+        assert!(matches!(
+            verify_ed25519_batch(&before),
+            Err(BatchVerificationError { index: 0, .. })
+        ));
+
+        // Weak public key, the identity, with a signature valid for non-strict
+        // verification: s = 0
+        let pk = PublicKeyEd25519(r.to_vec());
+        let signature = Ed25519Signature([r, [0; 32]].concat());
+        batch[1] = (&pk, &signature, msg);
+
+        assert!(matches!(
+            verify_ed25519_batch(&batch),
+            Err(BatchVerificationError { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_mixed_order_rejected() {
+        let (pk, sk) = &keys(1)[0];
+        let valid = signed(2);
+
+        for i in 0..16 {
+            let msg = format!("mixed order R {}", i).into_bytes();
+            let signature = mixed_order_signature(pk, sk, &msg);
+            assert!(!matches!(pk.verify_signature(&signature, &msg), Ok(true)));
+
+            let batch = [
+                (&valid[0].0, &valid[0].1, valid[0].2.as_slice()),
+                (pk, &signature, msg.as_slice()),
+                (&valid[1].0, &valid[1].1, valid[1].2.as_slice()),
+            ];
+            assert!(
+                matches!(
+                    verify_ed25519_batch(&batch),
+                    Err(BatchVerificationError { index: 1, .. })
+                ),
+                "message {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn test_batch_verifier_mixed_order_rejected() {
+        let valid = signed(2);
+        let (pk, sk) = &keys(1)[0];
+
+        let public_keys: Vec<PublicKey> = [&valid[0].0, pk, &valid[1].0]
+            .into_iter()
+            .map(|pk| PublicKey::Ed25519(pk.clone()))
+            .collect();
+        for i in 0..16 {
+            let msg = format!("mixed order R {}", i).into_bytes();
+            let signatures = [
+                Signature::Ed25519(valid[0].1.clone()),
+                Signature::Ed25519(mixed_order_signature(pk, sk, &msg)),
+                Signature::Ed25519(valid[1].1.clone()),
+            ];
+            let msgs = [valid[0].2.as_slice(), &msg, valid[1].2.as_slice()];
+
+            let mut verifier = BatchVerifier::new();
+            for ((pk, signature), msg) in public_keys.iter().zip(&signatures).zip(msgs) {
+                verifier.add(pk, signature, msg);
+            }
+            let results = verifier.verify();
+            assert!(results[0].is_ok() && results[2].is_ok(), "message {}", i);
+            assert!(results[1].is_err(), "message {}", i);
+        }
+    }
+
+    #[test]
+    fn test_batch_verifier() {
+        use crate::hash::{SecretKeyEd25519, SecretKeyP256, SecretKeySecp256k1, UnknownSignature};
+        use crate::secret_key::SecretKey;
+
+        let mut secret_keys = vec![];
+        for i in 1..4u8 {
+            secret_keys.push(SecretKey::Ed25519(
+                SecretKeyEd25519::try_from_bytes(&[i; 32]).unwrap(),
+            ));
+            secret_keys.push(SecretKey::Secp256k1(
+                SecretKeySecp256k1::try_from_bytes(&[i; 32]).unwrap(),
+            ));
+            secret_keys.push(SecretKey::P256(
+                SecretKeyP256::try_from_bytes(&[i; 32]).unwrap(),
+            ));
+            #[cfg(any(feature = "bls", feature = "bls-rust"))]
+            secret_keys.push(SecretKey::Bls(
+                crate::bls::keypair_from_ikm([i; 32]).unwrap().0,
+            ));
+        }
+
+        let msgs: Vec<Vec<u8>> = (0..secret_keys.len())
+            .map(|i| format!("operation {}", i).into_bytes())
+            .collect();
+        let public_keys: Vec<PublicKey> = secret_keys
+            .iter()
+            .map(|sk| sk.public_key().unwrap())
+            .collect();
+        let mut signatures: Vec<Signature> = secret_keys
+            .iter()
+            .zip(&msgs)
+            .map(|(sk, msg)| sk.sign(msg).unwrap())
+            .collect();
+
+        let verify = |signatures: &[Signature]| {
+            let mut verifier = BatchVerifier::new();
+            for ((pk, signature), msg) in public_keys.iter().zip(signatures).zip(&msgs) {
+                verifier.add(pk, signature, msg);
+            }
+            assert_eq!(verifier.len(), msgs.len());
+            verifier.verify()
+        };
+
+        assert!(BatchVerifier::new().verify().is_empty());
+        assert!(verify(&signatures).iter().all(Result::is_ok));
+
+        // Generic signature of an ed25519 key.
+        if let Signature::Ed25519(sig) = &signatures[0] {
+            signatures[0] = Signature::Unknown(UnknownSignature::from(sig.clone()));
+        }
+        assert!(verify(&signatures).iter().all(Result::is_ok));
+
+        // Signatures swapped between keys of the same curve, and between
+        // keys of different curves.
+        let step = if cfg!(any(feature = "bls", feature = "bls-rust")) {
+            4
+        } else {
+            3
+        };
+        let mut invalid = vec![];
+        for i in 0..step {
+            signatures.swap(i, i + step);
+            invalid.extend([i, i + step]);
+        }
+        signatures.swap(2 * step + 1, 2 * step + 2);
+        invalid.extend([2 * step + 1, 2 * step + 2]);
+
+        let results = verify(&signatures);
+        for (index, result) in results.iter().enumerate() {
+            assert_eq!(result.is_err(), invalid.contains(&index), "{}", index);
+        }
+        assert!(matches!(
+            results[2 * step + 1],
+            Err(CryptoError::InvalidSignature)
+        ));
+    }
+}
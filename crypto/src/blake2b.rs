@@ -3,6 +3,8 @@
 //
 // SPDX-License-Identifier: MIT
 
+use alloc::vec;
+use alloc::vec::Vec;
 use cryptoxide::blake2b::Blake2b;
 use cryptoxide::digest::Digest;
 use serde::{Deserialize, Serialize};
@@ -13,6 +15,10 @@ use thiserror::Error;
 pub enum Blake2bError {
     #[error("Output digest length must be between 16 and 64 bytes.")]
     InvalidLength,
+    #[error("Key length must be at most 64 bytes.")]
+    InvalidKeyLength,
+    #[error("Personalization length must be at most 16 bytes.")]
+    InvalidPersonalizationLength,
 }
 
 /// Generate digest of length 256 bits (32bytes) from arbitrary binary data
@@ -71,6 +77,85 @@ where
     Ok(result)
 }
 
+/// Incremental Blake2b hasher, with a digest of `N` bytes.
+///
+/// Unlike [digest] and [digest_all], the data does not need to be available at
+/// once, and the digest is a fixed-size array - so that no allocation is needed.
+///
+/// ```
+/// use tezos_crypto_rs::blake2b::{digest_256, Blake2bHasher};
+///
+/// let mut hasher = Blake2bHasher::<32>::new();
+/// hasher.update(b"hello");
+/// hasher.update(b" world");
+///
+/// assert_eq!(hasher.finalize().to_vec(), digest_256(b"hello world"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Blake2bHasher<const N: usize> {
+    state: blake2b_simd::State,
+}
+
+impl<const N: usize> Blake2bHasher<N> {
+    // Blake2b supports digests from 16 to 64 bytes: checked at compile time.
+    const VALID_LENGTH: () = assert!(N >= 16 && N <= 64, "invalid Blake2b digest length");
+
+    /// Create a hasher.
+    pub fn new() -> Self {
+        Self::with_params(&[], &[]).unwrap() // empty key and personalization are valid
+    }
+
+    /// Create a hasher in keyed mode, to compute a MAC. The key must be at most 64 bytes.
+    pub fn new_keyed(key: &[u8]) -> Result<Self, Blake2bError> {
+        Self::with_params(key, &[])
+    }
+
+    /// Create a hasher with a key, which may be empty, and a personalization of
+    /// at most 16 bytes, to separate the digests of different domains.
+    pub fn with_params(key: &[u8], personalization: &[u8]) -> Result<Self, Blake2bError> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_LENGTH;
+
+        if key.len() > blake2b_simd::KEYBYTES {
+            return Err(Blake2bError::InvalidKeyLength);
+        }
+        if personalization.len() > blake2b_simd::PERSONALBYTES {
+            return Err(Blake2bError::InvalidPersonalizationLength);
+        }
+
+        let state = blake2b_simd::Params::new()
+            .hash_length(N)
+            .key(key)
+            .personal(personalization)
+            .to_state();
+
+        Ok(Self { state })
+    }
+
+    /// Feed data to the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        self.state.update(data);
+    }
+
+    /// Write the digest of the data fed so far.
+    pub fn finalize_into(self, out: &mut [u8; N]) {
+        out.copy_from_slice(self.state.finalize().as_bytes());
+    }
+
+    /// Digest of the data fed so far.
+    pub fn finalize(self) -> [u8; N] {
+        let mut out = [0; N];
+        self.finalize_into(&mut out);
+        out
+    }
+}
+
+impl<const N: usize> Default for Blake2bHasher<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Computes a full binary tree from the list [xs].
 /// In this tree the ith leaf (from left to right) is the ith element of the
 /// list [xs]. If [xs] is the empty list, then the result is the empty tree. If
@@ -88,86 +173,12 @@ where
 //   /\  /\  /\  /\
 //  1 2  3 4 5 5 5 5
 //
-//
-// TODO: optimize it,
-// this implementation will calculate the same hash [5, 5] two times.
+// The padding subtrees are computed once, see [crate::merkle::MerkleTree].
 pub fn merkle_tree<Leaf>(list: &[Leaf]) -> Vec<u8>
 where
     Leaf: AsRef<[u8]>,
 {
-    use std::ops::{Index, RangeFrom, RangeTo};
-
-    // Helper for calculating merkle tree
-    // The wrapper around slice which repeats last item forever
-    struct RepeatingSlice<'a, Leaf>(pub &'a [Leaf]);
-
-    impl<'a, Leaf> Index<usize> for RepeatingSlice<'a, Leaf> {
-        type Output = Leaf;
-
-        fn index(&self, index: usize) -> &Self::Output {
-            if self.0.is_empty() {
-                panic!();
-            } else if index < self.0.len() {
-                self.0.index(index)
-            } else {
-                self.0.last().unwrap()
-            }
-        }
-    }
-
-    impl<'a, Leaf> Index<RangeFrom<usize>> for RepeatingSlice<'a, Leaf> {
-        type Output = [Leaf];
-
-        fn index(&self, index: RangeFrom<usize>) -> &Self::Output {
-            if self.0.is_empty() {
-                panic!();
-            } else if index.start < self.0.len() {
-                &self.0[index]
-            } else {
-                &self.0[(self.0.len() - 1)..]
-            }
-        }
-    }
-
-    impl<'a, Leaf> Index<RangeTo<usize>> for RepeatingSlice<'a, Leaf> {
-        type Output = [Leaf];
-
-        fn index(&self, index: RangeTo<usize>) -> &Self::Output {
-            if self.0.is_empty() {
-                panic!();
-            } else if index.end <= self.0.len() {
-                &self.0[index]
-            } else {
-                &self.0[..self.0.len()]
-            }
-        }
-    }
-
-    fn merkle_tree_inner<Leaf>(list: &RepeatingSlice<Leaf>, degree: u32) -> Vec<u8>
-    where
-        Leaf: AsRef<[u8]>,
-    {
-        match degree {
-            0 => digest_256(list[0].as_ref()),
-            d => {
-                let middle = 1 << (d - 1);
-                digest_all(
-                    &[
-                        merkle_tree_inner(&RepeatingSlice(&list[..middle]), d - 1),
-                        merkle_tree_inner(&RepeatingSlice(&list[middle..]), d - 1),
-                    ],
-                    32,
-                )
-                .unwrap() // we know length is within bounds, and that's the only error possible
-            }
-        }
-    }
-
-    if list.is_empty() {
-        digest_256(&[])
-    } else {
-        merkle_tree_inner(&RepeatingSlice(list), 64 - (list.len() - 1).leading_zeros())
-    }
+    crate::merkle::MerkleTree::new(list).root().to_vec()
 }
 
 #[cfg(test)]
@@ -221,4 +232,58 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn blake2b_hasher() {
+        let mut hasher = Blake2bHasher::<32>::new();
+        for chunk in [&b"hello"[..], b" ", b"world"] {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize().to_vec(), digest_256(b"hello world"));
+
+        let data = vec![b'x'; 1_000_000];
+        let mut hasher = Blake2bHasher::<32>::default();
+        data.chunks(4096).for_each(|chunk| hasher.update(chunk));
+        let mut out = [0; 32];
+        hasher.finalize_into(&mut out);
+        assert_eq!(out.to_vec(), digest_256(&data));
+
+        let mut hasher = Blake2bHasher::<16>::new();
+        hasher.update(b"hello world");
+        assert_eq!(hasher.finalize().to_vec(), digest_128(b"hello world"));
+    }
+
+    // Expected digests computed with Python's `hashlib.blake2b`.
+    #[test]
+    fn blake2b_hasher_keyed_and_personalized() {
+        let mut hasher = Blake2bHasher::<32>::new_keyed(b"secret key").unwrap();
+        hasher.update(b"hello world");
+        assert_eq!(
+            hex::encode(hasher.finalize()),
+            "612a5a7b4ac57f909bd3b43130676556620e13d2a86b55b028013751af06277d"
+        );
+
+        let mut hasher = Blake2bHasher::<32>::with_params(&[], b"tezos").unwrap();
+        hasher.update(b"hello world");
+        assert_eq!(
+            hex::encode(hasher.finalize()),
+            "631688a8b506ed7a29f069bd47f5e64b0ff5ad1f4de36e446b6c0c72393e5702"
+        );
+
+        let mut hasher = Blake2bHasher::<20>::with_params(b"secret key", b"tezos").unwrap();
+        hasher.update(b"hello world");
+        assert_eq!(
+            hex::encode(hasher.finalize()),
+            "0140352f9b0dace8ad007fc96b8e9f59fe8d7876"
+        );
+
+        assert_eq!(
+            Blake2bHasher::<32>::new_keyed(&[0; 65]).unwrap_err(),
+            Blake2bError::InvalidKeyLength
+        );
+        assert_eq!(
+            Blake2bHasher::<32>::with_params(&[], &[0; 17]).unwrap_err(),
+            Blake2bError::InvalidPersonalizationLength
+        );
+    }
 }
@@ -4,26 +4,35 @@
 // SPDX-License-Identifier: MIT
 
 //! BLS support (min_pk).
-
-#![cfg(feature = "bls")]
-
+//!
+//! The curve operations are provided either by [blst], with the `bls` feature,
+//! or by the pure-Rust [bls12_381] crate, with the `bls-rust` feature - for
+//! targets where blst cannot be built, such as `wasm32-unknown-unknown`.
+//! When both features are enabled, blst is used.
+//!
+//! [blst]: <https://crates.io/crates/blst>
+//! [bls12_381]: <https://crates.io/crates/bls12_381>
+
+#![cfg(any(feature = "bls", feature = "bls-rust"))]
+
+#[cfg(feature = "bls")]
+mod blst;
+#[cfg(any(feature = "bls-rust", test))]
+mod rust;
+
+#[cfg(feature = "bls")]
+use self::blst as backend;
+#[cfg(not(feature = "bls"))]
+use self::rust as backend;
+
+use crate::blake2b;
 use crate::hash::BlsSignature;
 use crate::hash::ContractTz4Hash;
 use crate::hash::PublicKeyBls;
 use crate::hash::SecretKeyBls;
 use crate::CryptoError;
 use crate::PublicKeyWithHash;
-use blst::min_pk;
-use blst::min_pk::{AggregateSignature, SecretKey};
-use blst::BLST_ERROR;
-
-impl TryFrom<&PublicKeyBls> for min_pk::PublicKey {
-    type Error = CryptoError;
-
-    fn try_from(source: &PublicKeyBls) -> Result<Self, Self::Error> {
-        min_pk::PublicKey::from_bytes(&source.0).map_err(|_| CryptoError::InvalidPublicKey)
-    }
-}
+use alloc::vec::Vec;
 
 /// A message and a public key for this message.
 ///
@@ -46,10 +55,15 @@ const AUG_CIPHER_SUITE: &str = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_";
 /// Pop signatures parameter value for `blst`
 ///
 /// Same constant as used for verify `Pop` in `bls12-381` ocaml package used in tezos.
-/// Not currently being used, but the mode is available in the tezos protocol.
-#[allow(dead_code)]
+/// This is the tag used for proofs of possession, required to reveal tz4 keys.
 const POP_CIPHER_SUITE: &str = "BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 
+/// Pop `dst` parameter for `blst`
+///
+/// Same constant as used for `Pop` in `bls12-381` ocaml package used in tezos.
+/// This is the mode used for signatures of a same message by several keys.
+const SIG_POP_CIPHER_SUITE: &str = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
 impl BlsSignature {
     /// Verify several messages with public keys and _one_ signature.
     ///
@@ -62,19 +76,7 @@ impl BlsSignature {
         &self,
         messages: &mut impl Iterator<Item = Message<'a>>,
     ) -> Result<bool, CryptoError> {
-        let signature: min_pk::Signature = self.try_into()?;
-
-        let messages_with_pk = messages
-            .map(|(message, public_key)| {
-                // For saftey, we ensure that each message is unique by prepending the
-                // public key.
-                let message_with_pk = prepend_public_key(message, public_key);
-
-                public_key
-                    .try_into()
-                    .map(move |public_key| (message_with_pk, public_key))
-            })
-            .collect::<Result<Vec<(Vec<u8>, min_pk::PublicKey)>, _>>()?;
+        let messages_with_pk = messages_with_pk(messages);
 
         let messages = messages_with_pk
             .iter()
@@ -83,44 +85,107 @@ impl BlsSignature {
 
         let public_keys = messages_with_pk
             .iter()
-            .map(|(_, pk)| pk)
+            .map(|(_, pk)| *pk)
             .collect::<Vec<_>>();
 
         // Tezos_crypto uses the Aug suite
         let dst = AUG_CIPHER_SUITE.as_bytes();
 
-        match signature.aggregate_verify(true, &messages, dst, &public_keys, true) {
-            BLST_ERROR::BLST_SUCCESS => Ok(true),
-            BLST_ERROR::BLST_VERIFY_FAIL => Ok(false),
-            err => Err(CryptoError::AlgorithmError(format!("BLS_ERROR: {:?}", err))),
-        }
+        backend::aggregate_verify(self, &messages, &public_keys, dst)
     }
 
-    /// Aggregate individual signatures into a single signature.
-    pub fn aggregate_sigs(sigs: &[&Self]) -> Result<Self, CryptoError> {
-        let sigs = sigs
+    /// Verify several signatures at once, each of one message with one public key.
+    ///
+    /// Simply aggregating the signatures would accept invalid signatures whose
+    /// errors cancel out. Instead, each signature is weighted by a scalar derived
+    /// from the whole batch, so that the batch is only valid when every signature
+    /// is valid on its own. The weights are 128-bit, for a 128-bit security level.
+    pub fn batch_verify<'a>(batch: &[(&'a Self, Message<'a>)]) -> Result<bool, CryptoError> {
+        if batch.is_empty() {
+            return Ok(true);
+        }
+
+        let signatures = batch
             .iter()
-            .map(|s| min_pk::Signature::try_from(*s))
-            .collect::<Result<Vec<_>, _>>()?;
+            .map(|(signature, _)| *signature)
+            .collect::<Vec<_>>();
 
-        let sigs = sigs.iter().collect::<Vec<_>>();
+        let messages_with_pk = messages_with_pk(&mut batch.iter().map(|(_, message)| *message));
 
-        let aggregate = AggregateSignature::aggregate(sigs.as_slice(), true)
-            .map_err(|e| CryptoError::AlgorithmError(format!("BLST_ERROR: {:?}", e)))?;
+        let messages = messages_with_pk
+            .iter()
+            .map(|(message, _)| message.as_slice())
+            .collect::<Vec<_>>();
 
-        aggregate
-            .validate()
-            .map_err(|e| CryptoError::AlgorithmError(format!("BLST_ERROR: {:?}", e)))?;
+        let public_keys = messages_with_pk
+            .iter()
+            .map(|(_, pk)| *pk)
+            .collect::<Vec<_>>();
+
+        // The weights are derived from all the inputs, so that they cannot be
+        // predicted when choosing the signatures.
+        let mut transcript = Vec::new();
+        for (signature, message) in signatures.iter().zip(messages.iter()) {
+            transcript.extend_from_slice(&blake2b::digest_256(message));
+            transcript.extend_from_slice(&signature.0);
+        }
+        let transcript = blake2b::digest_256(&transcript);
+
+        let weights = (0..batch.len() as u64)
+            .map(|i| {
+                let mut b = [0; 16];
+                let weight = blake2b::digest_256(&[&transcript[..], &i.to_le_bytes()].concat());
+                b.copy_from_slice(&weight[..16]);
+                // A zero weight would leave the signature unchecked.
+                u128::from_le_bytes(b) | 1
+            })
+            .collect::<Vec<_>>();
 
-        Ok(Self(aggregate.to_signature().compress().to_vec()))
+        backend::batch_verify(
+            &signatures,
+            &messages,
+            &public_keys,
+            &weights,
+            AUG_CIPHER_SUITE.as_bytes(),
+        )
+    }
+
+    /// Aggregate individual signatures into a single signature.
+    pub fn aggregate_sigs(sigs: &[&Self]) -> Result<Self, CryptoError> {
+        backend::aggregate_sigs(sigs)
+    }
+
+    /// Verify _one_ signature of the same message by several public keys,
+    /// as produced by [SecretKeyBls::pop_sign] and [BlsSignature::aggregate_sigs].
+    ///
+    /// The public keys are aggregated, so that this is a single pairing check.
+    /// They are not checked individually: to prevent rogue key attacks, their
+    /// proofs of possession must have been checked with [PublicKeyBls::pop_verify].
+    pub fn fast_aggregate_verify(
+        &self,
+        message: &[u8],
+        public_keys: &[PublicKeyBls],
+    ) -> Result<bool, CryptoError> {
+        backend::fast_aggregate_verify(self, message, public_keys, SIG_POP_CIPHER_SUITE.as_bytes())
     }
 }
 
-impl TryFrom<&BlsSignature> for min_pk::Signature {
-    type Error = CryptoError;
+impl PublicKeyBls {
+    /// Aggregate public keys into a single public key, against which the aggregate
+    /// of their signatures of a same message can be verified.
+    ///
+    /// As for [BlsSignature::fast_aggregate_verify], the proofs of possession of
+    /// the public keys must have been checked.
+    pub fn aggregate_pks(pks: &[&Self]) -> Result<Self, CryptoError> {
+        backend::aggregate_pks(pks)
+    }
 
-    fn try_from(value: &BlsSignature) -> Result<Self, Self::Error> {
-        min_pk::Signature::from_bytes(&value.0).map_err(|_| CryptoError::InvalidSignature)
+    /// Verify a proof of possession of the secret key of this public key, as
+    /// produced by [SecretKeyBls::pop_prove].
+    ///
+    /// The public key is checked to be in the group, and not the identity.
+    pub fn pop_verify(&self, proof: &BlsSignature) -> Result<bool, CryptoError> {
+        backend::verify(proof, &self.0, self, POP_CIPHER_SUITE.as_bytes())
     }
 }
 
@@ -128,27 +193,47 @@ impl TryFrom<&BlsSignature> for min_pk::Signature {
 impl SecretKeyBls {
     /// Derive the public key for the current secret key.
     pub fn derive_pk(&self) -> Result<PublicKeyBls, CryptoError> {
-        let sk = SecretKey::from_bytes(&self.0)
-            .map_err(|e| CryptoError::AlgorithmError(format!("BLST_ERROR: {:?}", e)))?;
-
-        let pk = sk.sk_to_pk();
-
-        Ok(PublicKeyBls(pk.to_bytes().to_vec()))
+        backend::derive_pk(self)
     }
 
     /// Sign the given data.
     pub fn sign(&self, message: impl AsRef<[u8]>) -> Result<BlsSignature, CryptoError> {
-        let sk = SecretKey::from_bytes(&self.0)
-            .map_err(|e| CryptoError::AlgorithmError(format!("BLST_ERROR: {:?}", e)))?;
+        let pk = backend::derive_pk(self)?;
+        let msg = prepend_public_key(message.as_ref(), &pk);
+
+        backend::sign(self, &msg, AUG_CIPHER_SUITE.as_bytes())
+    }
+
+    /// Sign the given data with the `Pop` scheme.
+    ///
+    /// Unlike [SecretKeyBls::sign], the message is not prefixed by the public key,
+    /// so that the signatures of a same message by several keys can be aggregated
+    /// and checked at once with [BlsSignature::fast_aggregate_verify].
+    pub fn pop_sign(&self, message: impl AsRef<[u8]>) -> Result<BlsSignature, CryptoError> {
+        backend::sign(self, message.as_ref(), SIG_POP_CIPHER_SUITE.as_bytes())
+    }
 
-        let pk = sk.sk_to_pk();
-        let msg = prepend_public_key(message.as_ref(), &PublicKeyBls(pk.to_bytes().to_vec()));
-        let sig = sk.sign(&msg, AUG_CIPHER_SUITE.as_bytes(), &[]);
+    /// Prove the possession of this secret key, by signing its public key with
+    /// the `Pop` tag - as done by octez for the reveal of tz4 keys.
+    pub fn pop_prove(&self) -> Result<BlsSignature, CryptoError> {
+        let pk = backend::derive_pk(self)?;
 
-        Ok(BlsSignature(sig.to_bytes().to_vec()))
+        backend::sign(self, &pk.0, POP_CIPHER_SUITE.as_bytes())
     }
 }
 
+fn messages_with_pk<'a>(
+    messages: &mut impl Iterator<Item = Message<'a>>,
+) -> Vec<(Vec<u8>, &'a PublicKeyBls)> {
+    messages
+        .map(|(message, public_key)| {
+            // For saftey, we ensure that each message is unique by prepending the
+            // public key.
+            (prepend_public_key(message, public_key), public_key)
+        })
+        .collect()
+}
+
 // We prepend each message with the public key used to sign it.
 fn prepend_public_key(msg: &[u8], pk: &PublicKeyBls) -> Vec<u8> {
     let mut message_with_pk =
@@ -160,13 +245,8 @@ fn prepend_public_key(msg: &[u8], pk: &PublicKeyBls) -> Vec<u8> {
 
 /// Generate a keypair from initial key material.
 pub fn keypair_from_ikm(ikm: [u8; 32]) -> Result<(SecretKeyBls, PublicKeyBls), CryptoError> {
-    let sk = SecretKey::key_gen(&ikm, &[])
-        .map_err(|e| CryptoError::AlgorithmError(format!("BLST_ERROR: {:?}", e)))?;
-
-    let pk = sk.sk_to_pk();
-
-    let pk = PublicKeyBls(pk.to_bytes().to_vec());
-    let sk = SecretKeyBls(sk.to_bytes().to_vec());
+    let sk = backend::keygen(&ikm)?;
+    let pk = backend::derive_pk(&sk)?;
 
     Ok((sk, pk))
 }
@@ -238,100 +318,6 @@ mod tests {
     };
     use proptest::prelude::*;
 
-    #[test]
-    fn decoding_invalid_signature_gives_error() {
-        use super::CryptoError;
-
-        let bytes: [u8; 96] = [
-            212, 164, 10, 104, 205, 205, 23, 255, 218, 184, 156, 159, 150, 133, 185, 31, 221, 34,
-            11, 39, 189, 17, 16, 28, 109, 72, 109, 48, 239, 105, 121, 121, 100, 149, 1, 168, 106,
-            118, 145, 148, 182, 122, 206, 83, 8, 214, 146, 238, 181, 41, 182, 23, 221, 66, 47, 99,
-            179, 9, 195, 96, 141, 204, 99, 53, 222, 157, 64, 102, 177, 118, 26, 240, 235, 189, 109,
-            214, 229, 77, 77, 24, 53, 136, 220, 124, 102, 108, 5, 241, 185, 98, 145, 206, 121, 169,
-            11, 255,
-        ];
-
-        let sig = BlsSignature(bytes.to_vec());
-        let sig = min_pk::Signature::try_from(&sig);
-
-        assert!(matches!(sig, Err(CryptoError::InvalidSignature)));
-    }
-
-    #[test]
-    fn decoding_valid_signature_is_ok() {
-        let bytes: [u8; 96] = [
-            149, 240, 234, 160, 166, 30, 18, 15, 229, 113, 68, 192, 204, 118, 169, 78, 252, 237,
-            251, 111, 240, 127, 236, 68, 231, 114, 243, 76, 61, 156, 148, 34, 203, 153, 6, 255,
-            159, 108, 21, 71, 163, 120, 87, 133, 239, 135, 225, 127, 14, 126, 215, 20, 107, 206,
-            222, 198, 187, 11, 173, 56, 167, 119, 182, 55, 57, 102, 180, 194, 18, 91, 49, 59, 130,
-            39, 33, 103, 243, 211, 156, 164, 53, 160, 255, 198, 58, 2, 124, 121, 201, 44, 139, 167,
-            48, 52, 211, 178,
-        ];
-
-        let sig = BlsSignature(bytes.to_vec());
-        let sig = min_pk::Signature::try_from(&sig);
-
-        assert!(sig.is_ok());
-    }
-
-    #[test]
-    fn return_error_on_invalid_public_key_encoding() {
-        use super::{min_pk, PublicKeyBls};
-
-        let bytes: [u8; 48] = [
-            118, 187, 155, 125, 42, 190, 144, 143, 145, 250, 125, 184, 90, 9, 210, 24, 202, 72, 22,
-            137, 121, 174, 233, 107, 175, 63, 167, 107, 192, 38, 60, 102, 74, 213, 169, 88, 51,
-            181, 190, 79, 226, 209, 166, 137, 54, 88, 14, 28,
-        ];
-
-        let pk = PublicKeyBls(bytes.to_vec());
-        let pk = min_pk::PublicKey::try_from(&pk);
-
-        assert!(matches!(pk, Err(CryptoError::InvalidPublicKey)));
-    }
-
-    #[test]
-    fn decode_valid_public_key_is_ok() {
-        let bytes: [u8; 48] = [
-            145, 150, 3, 232, 142, 40, 236, 191, 116, 53, 15, 47, 240, 143, 182, 94, 110, 121, 160,
-            108, 247, 42, 34, 231, 133, 156, 81, 111, 62, 109, 59, 223, 198, 220, 89, 7, 173, 251,
-            241, 82, 161, 86, 161, 40, 141, 57, 145, 123,
-        ];
-
-        let pk = PublicKeyBls(bytes.to_vec());
-        let pk = min_pk::PublicKey::try_from(&pk);
-
-        assert!(pk.is_ok());
-    }
-
-    #[test]
-    fn can_verify_signature_is_true() {
-        use super::AUG_CIPHER_SUITE;
-        use blst::min_pk::SecretKey;
-
-        let ikm: [u8; 32] = [
-            206, 83, 215, 142, 19, 242, 183, 160, 92, 186, 87, 192, 89, 109, 82, 0, 17, 60, 248,
-            194, 149, 144, 24, 238, 202, 18, 75, 107, 139, 241, 104, 198,
-        ];
-
-        let sk = SecretKey::key_gen(&ikm, &[]).unwrap();
-        let pk = PublicKeyBls(sk.sk_to_pk().to_bytes().to_vec());
-
-        let dst = AUG_CIPHER_SUITE.as_bytes();
-
-        let msg = b"blst is such a blast";
-        let mut signed_bytes = Vec::new();
-        signed_bytes.extend_from_slice(&pk.0);
-        signed_bytes.extend_from_slice(msg);
-
-        let sig = BlsSignature(sk.sign(&signed_bytes, dst, &[]).to_bytes().to_vec());
-
-        let msg_keys = [(&msg[..], &pk)];
-        let res = sig.aggregate_verify(&mut msg_keys.into_iter());
-
-        assert!(matches!(res, Ok(true)));
-    }
-
     // Values taken from tezt test, that was failing due to public key not being
     // prepended to msg.
     #[test]
@@ -383,34 +369,93 @@ mod tests {
         );
     }
 
+    // Proofs computed independently of blst, by hashing the public key to G2
+    // with the `Pop` tag and multiplying by the secret key, using the `bls12_381` crate.
     #[test]
-    fn can_verify_signature_is_false() {
-        use blst::min_pk::SecretKey;
-
-        let ikm: [u8; 32] = [
-            139, 238, 61, 128, 196, 109, 58, 44, 13, 240, 207, 148, 246, 216, 242, 161, 132, 197,
-            169, 201, 120, 146, 252, 112, 92, 255, 57, 102, 202, 178, 210, 113,
-        ];
+    fn pop_prove_and_verify() {
+        let test = |sk: &str, pk: &str, proof: &str| {
+            let sk = SecretKeyBls::from_b58check(sk).unwrap();
+            let pk = PublicKeyBls::from_b58check(pk).unwrap();
+            let proof = BlsSignature::from_b58check(proof).unwrap();
+
+            assert_eq!(sk.derive_pk().unwrap(), pk);
+            assert_eq!(sk.pop_prove().unwrap(), proof);
+            assert!(matches!(pk.pop_verify(&proof), Ok(true)));
+        };
 
-        let signature_bytes: [u8; 96] = [
-            149, 240, 234, 160, 166, 30, 18, 15, 229, 113, 68, 192, 204, 118, 169, 78, 252, 237,
-            251, 111, 240, 127, 236, 68, 231, 114, 243, 76, 61, 156, 148, 34, 203, 153, 6, 255,
-            159, 108, 21, 71, 163, 120, 87, 133, 239, 135, 225, 127, 14, 126, 215, 20, 107, 206,
-            222, 198, 187, 11, 173, 56, 167, 119, 182, 55, 57, 102, 180, 194, 18, 91, 49, 59, 130,
-            39, 33, 103, 243, 211, 156, 164, 53, 160, 255, 198, 58, 2, 124, 121, 201, 44, 139, 167,
-            48, 52, 211, 178,
-        ];
+        test(
+            "BLsk25rZGNMAdKnSD6oR1ozPeL6aut1UhaNo38UCo8BBpA4UxifTfL",
+            "BLpk1uuVQTVNTP9WrXeu9jQ8PDFem69ahW5aD6jnh13YYqUhRMWUmVx3onaoonMxGpiBxt65P2ya",
+            "BLsig9ZQAvgzs2W3zLWWYcb4MpARP2jJhdg2DgcKXffHda81qKTnMyM1F2GrrwQRR44tTu3P4qgEPeDvCpijEzrEbTu8UuLpgrcXWbz9KBQWQfmFVgkjKF9Je6aNdRQ2ju782iMLUciaPo",
+        );
+        test(
+            "BLsk1erDDQsKxTwrpsLmiyt1deFvFonfhgG46stGZg4K5vgYbKAwNb",
+            "BLpk1qqW4ebiQL5vzoMZZQW2hk1pCqRAaLgKQGKDJN3wDtTmdHfFhh6hJtZqhwBpHGWTimUBiBdM",
+            "BLsig9gGQFfXE16rZLX2zHSnVf459VYRk79j2YdMZKbdujUSS85s3BX4uzvDi2WbDNoGZ1AHEP2ZYb45VCtwJKuyFxSZEMtrwiST7B97QNF45sZhcZQBsbw9kUQbP3ZsoCNZR6jQi7fi88",
+        );
+        test(
+            "BLsk1mfE7oMJRY6j6WKFALeAjhJXmwAsPTdz8X1YtHPVW7LkWAzd1P",
+            "BLpk1uxbEhxZEbJNpPK4oebgfpQrCuu8ydnrzXYGY4mMpbFG6GQUhEnxsHWQLE8PfUy8j3MDopNY",
+            "BLsig9WMhG66fimJ626CrSatCMQudpvNKPehL4Sq6bsFjiGbPj1oAZhQsY9BJtDgvkVBCLUvNz4LBajtZ4D3vXs1DiYNGfenTSN3gRnJMbxqv42ovQDjAr9bSDCMerPaRcy2U76pqhUEzu",
+        );
+    }
 
-        let sk = SecretKey::key_gen(&ikm, &[]).unwrap();
-        let pk = PublicKeyBls(sk.sk_to_pk().to_bytes().to_vec());
+    #[test]
+    fn pop_verify_rejects_other_proofs() {
+        let (sk, pk) = keypair_from_ikm([1; 32]).unwrap();
+        let (other_sk, other_pk) = keypair_from_ikm([2; 32]).unwrap();
+
+        let proof = sk.pop_prove().unwrap();
+        assert!(matches!(other_pk.pop_verify(&proof), Ok(false)));
+        assert!(matches!(
+            pk.pop_verify(&other_sk.pop_prove().unwrap()),
+            Ok(false)
+        ));
+
+        // A signature of the public key, with the Aug tag, is not a proof.
+        let sig = sk.sign(&pk.0).unwrap();
+        assert!(matches!(pk.pop_verify(&sig), Ok(false)));
+    }
 
-        let msg = b"blst is such a blast";
-        let sig = BlsSignature(signature_bytes.to_vec());
+    #[test]
+    fn fast_aggregate_verify_same_message() {
+        let msg = b"attestation";
+        let keys = (1..5u8)
+            .map(|i| keypair_from_ikm([i; 32]).unwrap())
+            .collect::<Vec<_>>();
+        let pks = keys.iter().map(|(_, pk)| pk.clone()).collect::<Vec<_>>();
+        let sigs = keys
+            .iter()
+            .map(|(sk, _)| sk.pop_sign(msg).unwrap())
+            .collect::<Vec<_>>();
 
-        let msg_keys = [(&msg[..], &pk)];
-        let res = sig.aggregate_verify(&mut msg_keys.into_iter());
+        // Computed independently of blst, as for the proofs of possession.
+        assert_eq!(
+            sigs[0].to_b58check(),
+            "BLsig9qR4EwzS19UCJQMW1zanoReSGwetgkxaiHFQ5JBQxY37jQ14E2n2hLkDDWz4MKdiynDgD6qmJaNyGMtHu7mpuWxrr2CisdmsqiTiXy1oWEaq6u5LdRJSk853rPuCbN4URiUVypNKS"
+        );
 
-        assert!(matches!(res, Ok(false)));
+        let sig = BlsSignature::aggregate_sigs(&sigs.iter().collect::<Vec<_>>()).unwrap();
+        assert!(matches!(sig.fast_aggregate_verify(msg, &pks), Ok(true)));
+        assert!(matches!(
+            sig.fast_aggregate_verify(b"other", &pks),
+            Ok(false)
+        ));
+        assert!(matches!(
+            sig.fast_aggregate_verify(msg, &pks[1..]),
+            Ok(false)
+        ));
+
+        let pk = PublicKeyBls::aggregate_pks(&pks.iter().collect::<Vec<_>>()).unwrap();
+        assert!(matches!(sig.fast_aggregate_verify(msg, &[pk]), Ok(true)));
+
+        // Signatures of the Aug scheme are bound to their public key.
+        let sigs = keys
+            .iter()
+            .map(|(sk, _)| sk.sign(msg).unwrap())
+            .collect::<Vec<_>>();
+        let sig = BlsSignature::aggregate_sigs(&sigs.iter().collect::<Vec<_>>()).unwrap();
+        assert!(matches!(sig.fast_aggregate_verify(msg, &pks), Ok(false)));
     }
 
     // Test to ensure that we use the correct hashing scheme to convert between
@@ -494,6 +539,34 @@ mod tests {
           assert!(matches!(res, Ok(true)));
       }
 
+      #[test]
+      fn batch_verify_rejects_swapped_signatures(
+          (fst_sk, fst_pk) in bls_arb_keypair(),
+          (snd_sk, snd_pk) in bls_arb_keypair(),
+          fst_msg in any::<Vec<u8>>(),
+          snd_msg in any::<Vec<u8>>(),
+      ) {
+          let sig1 = fst_sk.sign(fst_msg.as_slice()).unwrap();
+          let sig2 = snd_sk.sign(snd_msg.as_slice()).unwrap();
+
+          let batch = [
+              (&sig1, (fst_msg.as_slice(), &fst_pk)),
+              (&sig2, (snd_msg.as_slice(), &snd_pk)),
+          ];
+          assert!(matches!(BlsSignature::batch_verify(&batch), Ok(true)));
+
+          // The aggregate of the swapped signatures is still valid, but the
+          // signatures themselves are not.
+          let swapped = [
+              (&sig2, (fst_msg.as_slice(), &fst_pk)),
+              (&sig1, (snd_msg.as_slice(), &snd_pk)),
+          ];
+          let aggregate = BlsSignature::aggregate_sigs(&[&sig2, &sig1]).unwrap();
+          let msg_keys = [(fst_msg.as_slice(), &fst_pk), (snd_msg.as_slice(), &snd_pk)];
+          assert!(matches!(aggregate.aggregate_verify(&mut msg_keys.into_iter()), Ok(true)));
+          assert!(matches!(BlsSignature::batch_verify(&swapped), Ok(false)));
+      }
+
       #[test]
       fn verify_signature_fails_with_wrong_pk(
           (signing_sk, _) in bls_arb_keypair(),
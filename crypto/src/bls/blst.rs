@@ -0,0 +1,305 @@
+// SPDX-FileCopyrightText: 2022-2023 TriliTech <contact@trili.tech>
+// SPDX-FileCopyrightText: 2023 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! BLS backend based on [blst](https://crates.io/crates/blst).
+
+use crate::hash::BlsSignature;
+use crate::hash::PublicKeyBls;
+use crate::hash::SecretKeyBls;
+use crate::CryptoError;
+use alloc::format;
+use alloc::vec::Vec;
+use blst::min_pk;
+use blst::min_pk::{AggregatePublicKey, AggregateSignature, SecretKey};
+use blst::BLST_ERROR;
+
+/// Size of the weights of [batch_verify].
+const BATCH_WEIGHT_BITS: usize = 128;
+
+impl TryFrom<&PublicKeyBls> for min_pk::PublicKey {
+    type Error = CryptoError;
+
+    fn try_from(source: &PublicKeyBls) -> Result<Self, Self::Error> {
+        min_pk::PublicKey::from_bytes(&source.0).map_err(|_| CryptoError::InvalidPublicKey)
+    }
+}
+
+impl TryFrom<&BlsSignature> for min_pk::Signature {
+    type Error = CryptoError;
+
+    fn try_from(value: &BlsSignature) -> Result<Self, Self::Error> {
+        min_pk::Signature::from_bytes(&value.0).map_err(|_| CryptoError::InvalidSignature)
+    }
+}
+
+fn secret_key(sk: &SecretKeyBls) -> Result<SecretKey, CryptoError> {
+    SecretKey::from_bytes(&sk.0)
+        .map_err(|e| CryptoError::AlgorithmError(format!("BLST_ERROR: {:?}", e)))
+}
+
+fn public_keys<'a>(
+    pks: impl IntoIterator<Item = &'a PublicKeyBls>,
+) -> Result<Vec<min_pk::PublicKey>, CryptoError> {
+    pks.into_iter().map(min_pk::PublicKey::try_from).collect()
+}
+
+fn verification_result(err: BLST_ERROR) -> Result<bool, CryptoError> {
+    match err {
+        BLST_ERROR::BLST_SUCCESS => Ok(true),
+        BLST_ERROR::BLST_VERIFY_FAIL => Ok(false),
+        err => Err(CryptoError::AlgorithmError(format!("BLS_ERROR: {:?}", err))),
+    }
+}
+
+pub(super) fn keygen(ikm: &[u8]) -> Result<SecretKeyBls, CryptoError> {
+    let sk = SecretKey::key_gen(ikm, &[])
+        .map_err(|e| CryptoError::AlgorithmError(format!("BLST_ERROR: {:?}", e)))?;
+
+    Ok(SecretKeyBls(sk.to_bytes().to_vec()))
+}
+
+pub(super) fn derive_pk(sk: &SecretKeyBls) -> Result<PublicKeyBls, CryptoError> {
+    let pk = secret_key(sk)?.sk_to_pk();
+
+    Ok(PublicKeyBls(pk.to_bytes().to_vec()))
+}
+
+pub(super) fn sign(sk: &SecretKeyBls, msg: &[u8], dst: &[u8]) -> Result<BlsSignature, CryptoError> {
+    let sig = secret_key(sk)?.sign(msg, dst, &[]);
+
+    Ok(BlsSignature(sig.to_bytes().to_vec()))
+}
+
+pub(super) fn verify(
+    sig: &BlsSignature,
+    msg: &[u8],
+    pk: &PublicKeyBls,
+    dst: &[u8],
+) -> Result<bool, CryptoError> {
+    let pk: min_pk::PublicKey = pk.try_into()?;
+    let sig: min_pk::Signature = sig.try_into()?;
+
+    verification_result(sig.verify(true, msg, dst, &[], &pk, true))
+}
+
+pub(super) fn aggregate_verify(
+    sig: &BlsSignature,
+    msgs: &[&[u8]],
+    pks: &[&PublicKeyBls],
+    dst: &[u8],
+) -> Result<bool, CryptoError> {
+    let sig: min_pk::Signature = sig.try_into()?;
+    let pks = public_keys(pks.iter().copied())?;
+    let pks = pks.iter().collect::<Vec<_>>();
+
+    verification_result(sig.aggregate_verify(true, msgs, dst, &pks, true))
+}
+
+pub(super) fn fast_aggregate_verify(
+    sig: &BlsSignature,
+    msg: &[u8],
+    pks: &[PublicKeyBls],
+    dst: &[u8],
+) -> Result<bool, CryptoError> {
+    let sig: min_pk::Signature = sig.try_into()?;
+    let pks = public_keys(pks)?;
+    let pks = pks.iter().collect::<Vec<_>>();
+
+    verification_result(sig.fast_aggregate_verify(true, msg, dst, &pks))
+}
+
+pub(super) fn batch_verify(
+    sigs: &[&BlsSignature],
+    msgs: &[&[u8]],
+    pks: &[&PublicKeyBls],
+    weights: &[u128],
+    dst: &[u8],
+) -> Result<bool, CryptoError> {
+    let sigs = sigs
+        .iter()
+        .map(|sig| min_pk::Signature::try_from(*sig))
+        .collect::<Result<Vec<_>, _>>()?;
+    let sigs = sigs.iter().collect::<Vec<_>>();
+
+    let pks = public_keys(pks.iter().copied())?;
+    let pks = pks.iter().collect::<Vec<_>>();
+
+    let rands = weights
+        .iter()
+        .map(|weight| {
+            let mut b = [0; 32];
+            b[..BATCH_WEIGHT_BITS / 8].copy_from_slice(&weight.to_le_bytes());
+            blst::blst_scalar { b }
+        })
+        .collect::<Vec<_>>();
+
+    verification_result(min_pk::Signature::verify_multiple_aggregate_signatures(
+        msgs,
+        dst,
+        &pks,
+        true,
+        &sigs,
+        true,
+        &rands,
+        BATCH_WEIGHT_BITS,
+    ))
+}
+
+pub(super) fn aggregate_sigs(sigs: &[&BlsSignature]) -> Result<BlsSignature, CryptoError> {
+    let sigs = sigs
+        .iter()
+        .map(|s| min_pk::Signature::try_from(*s))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let sigs = sigs.iter().collect::<Vec<_>>();
+
+    let aggregate = AggregateSignature::aggregate(sigs.as_slice(), true)
+        .map_err(|e| CryptoError::AlgorithmError(format!("BLST_ERROR: {:?}", e)))?;
+
+    aggregate
+        .validate()
+        .map_err(|e| CryptoError::AlgorithmError(format!("BLST_ERROR: {:?}", e)))?;
+
+    Ok(BlsSignature(aggregate.to_signature().compress().to_vec()))
+}
+
+pub(super) fn aggregate_pks(pks: &[&PublicKeyBls]) -> Result<PublicKeyBls, CryptoError> {
+    let pks = public_keys(pks.iter().copied())?;
+    let pks = pks.iter().collect::<Vec<_>>();
+
+    let aggregate = AggregatePublicKey::aggregate(pks.as_slice(), true)
+        .map_err(|e| CryptoError::AlgorithmError(format!("BLST_ERROR: {:?}", e)))?;
+
+    Ok(PublicKeyBls(aggregate.to_public_key().compress().to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decoding_invalid_signature_gives_error() {
+        use super::CryptoError;
+
+        let bytes: [u8; 96] = [
+            212, 164, 10, 104, 205, 205, 23, 255, 218, 184, 156, 159, 150, 133, 185, 31, 221, 34,
+            11, 39, 189, 17, 16, 28, 109, 72, 109, 48, 239, 105, 121, 121, 100, 149, 1, 168, 106,
+            118, 145, 148, 182, 122, 206, 83, 8, 214, 146, 238, 181, 41, 182, 23, 221, 66, 47, 99,
+            179, 9, 195, 96, 141, 204, 99, 53, 222, 157, 64, 102, 177, 118, 26, 240, 235, 189, 109,
+            214, 229, 77, 77, 24, 53, 136, 220, 124, 102, 108, 5, 241, 185, 98, 145, 206, 121, 169,
+            11, 255,
+        ];
+
+        let sig = BlsSignature(bytes.to_vec());
+        let sig = min_pk::Signature::try_from(&sig);
+
+        assert!(matches!(sig, Err(CryptoError::InvalidSignature)));
+    }
+
+    #[test]
+    fn decoding_valid_signature_is_ok() {
+        let bytes: [u8; 96] = [
+            149, 240, 234, 160, 166, 30, 18, 15, 229, 113, 68, 192, 204, 118, 169, 78, 252, 237,
+            251, 111, 240, 127, 236, 68, 231, 114, 243, 76, 61, 156, 148, 34, 203, 153, 6, 255,
+            159, 108, 21, 71, 163, 120, 87, 133, 239, 135, 225, 127, 14, 126, 215, 20, 107, 206,
+            222, 198, 187, 11, 173, 56, 167, 119, 182, 55, 57, 102, 180, 194, 18, 91, 49, 59, 130,
+            39, 33, 103, 243, 211, 156, 164, 53, 160, 255, 198, 58, 2, 124, 121, 201, 44, 139, 167,
+            48, 52, 211, 178,
+        ];
+
+        let sig = BlsSignature(bytes.to_vec());
+        let sig = min_pk::Signature::try_from(&sig);
+
+        assert!(sig.is_ok());
+    }
+
+    #[test]
+    fn return_error_on_invalid_public_key_encoding() {
+        use super::{min_pk, PublicKeyBls};
+
+        let bytes: [u8; 48] = [
+            118, 187, 155, 125, 42, 190, 144, 143, 145, 250, 125, 184, 90, 9, 210, 24, 202, 72, 22,
+            137, 121, 174, 233, 107, 175, 63, 167, 107, 192, 38, 60, 102, 74, 213, 169, 88, 51,
+            181, 190, 79, 226, 209, 166, 137, 54, 88, 14, 28,
+        ];
+
+        let pk = PublicKeyBls(bytes.to_vec());
+        let pk = min_pk::PublicKey::try_from(&pk);
+
+        assert!(matches!(pk, Err(CryptoError::InvalidPublicKey)));
+    }
+
+    #[test]
+    fn decode_valid_public_key_is_ok() {
+        let bytes: [u8; 48] = [
+            145, 150, 3, 232, 142, 40, 236, 191, 116, 53, 15, 47, 240, 143, 182, 94, 110, 121, 160,
+            108, 247, 42, 34, 231, 133, 156, 81, 111, 62, 109, 59, 223, 198, 220, 89, 7, 173, 251,
+            241, 82, 161, 86, 161, 40, 141, 57, 145, 123,
+        ];
+
+        let pk = PublicKeyBls(bytes.to_vec());
+        let pk = min_pk::PublicKey::try_from(&pk);
+
+        assert!(pk.is_ok());
+    }
+
+    #[test]
+    fn can_verify_signature_is_true() {
+        use crate::bls::AUG_CIPHER_SUITE;
+        use blst::min_pk::SecretKey;
+
+        let ikm: [u8; 32] = [
+            206, 83, 215, 142, 19, 242, 183, 160, 92, 186, 87, 192, 89, 109, 82, 0, 17, 60, 248,
+            194, 149, 144, 24, 238, 202, 18, 75, 107, 139, 241, 104, 198,
+        ];
+
+        let sk = SecretKey::key_gen(&ikm, &[]).unwrap();
+        let pk = PublicKeyBls(sk.sk_to_pk().to_bytes().to_vec());
+
+        let dst = AUG_CIPHER_SUITE.as_bytes();
+
+        let msg = b"blst is such a blast";
+        let mut signed_bytes = Vec::new();
+        signed_bytes.extend_from_slice(&pk.0);
+        signed_bytes.extend_from_slice(msg);
+
+        let sig = BlsSignature(sk.sign(&signed_bytes, dst, &[]).to_bytes().to_vec());
+
+        let msg_keys = [(&msg[..], &pk)];
+        let res = sig.aggregate_verify(&mut msg_keys.into_iter());
+
+        assert!(matches!(res, Ok(true)));
+    }
+
+    #[test]
+    fn can_verify_signature_is_false() {
+        use blst::min_pk::SecretKey;
+
+        let ikm: [u8; 32] = [
+            139, 238, 61, 128, 196, 109, 58, 44, 13, 240, 207, 148, 246, 216, 242, 161, 132, 197,
+            169, 201, 120, 146, 252, 112, 92, 255, 57, 102, 202, 178, 210, 113,
+        ];
+
+        let signature_bytes: [u8; 96] = [
+            149, 240, 234, 160, 166, 30, 18, 15, 229, 113, 68, 192, 204, 118, 169, 78, 252, 237,
+            251, 111, 240, 127, 236, 68, 231, 114, 243, 76, 61, 156, 148, 34, 203, 153, 6, 255,
+            159, 108, 21, 71, 163, 120, 87, 133, 239, 135, 225, 127, 14, 126, 215, 20, 107, 206,
+            222, 198, 187, 11, 173, 56, 167, 119, 182, 55, 57, 102, 180, 194, 18, 91, 49, 59, 130,
+            39, 33, 103, 243, 211, 156, 164, 53, 160, 255, 198, 58, 2, 124, 121, 201, 44, 139, 167,
+            48, 52, 211, 178,
+        ];
+
+        let sk = SecretKey::key_gen(&ikm, &[]).unwrap();
+        let pk = PublicKeyBls(sk.sk_to_pk().to_bytes().to_vec());
+
+        let msg = b"blst is such a blast";
+        let sig = BlsSignature(signature_bytes.to_vec());
+
+        let msg_keys = [(&msg[..], &pk)];
+        let res = sig.aggregate_verify(&mut msg_keys.into_iter());
+
+        assert!(matches!(res, Ok(false)));
+    }
+}
@@ -0,0 +1,411 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Pure-Rust BLS backend, based on [bls12_381](https://crates.io/crates/bls12_381).
+//!
+//! Produces the same keys and signatures as the [blst](super::blst) backend, and
+//! accepts the same signatures.
+
+// With the `bls` feature, this backend is only built for the equivalence tests.
+#![cfg_attr(feature = "bls", allow(dead_code))]
+
+use crate::hash::BlsSignature;
+use crate::hash::PublicKeyBls;
+use crate::hash::SecretKeyBls;
+use crate::CryptoError;
+use alloc::format;
+use alloc::vec::Vec;
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Gt, Scalar,
+};
+use cryptoxide::hkdf::{hkdf_expand, hkdf_extract};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+/// Salt of the `KeyGen` of the BLS signature draft, as used by blst.
+const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+
+fn algorithm_error(reason: &str) -> CryptoError {
+    CryptoError::AlgorithmError(format!("BLS_ERROR: {}", reason))
+}
+
+// Secret keys are big-endian scalars, as in blst.
+fn secret_key(sk: &SecretKeyBls) -> Result<Scalar, CryptoError> {
+    let mut bytes: [u8; 32] =
+        sk.0.as_slice()
+            .try_into()
+            .map_err(|_| algorithm_error("invalid secret key length"))?;
+    bytes.reverse();
+
+    let sk = Option::<Scalar>::from(Scalar::from_bytes(&bytes));
+    bytes.zeroize();
+    sk.filter(|sk| *sk != Scalar::zero())
+        .ok_or_else(|| algorithm_error("invalid secret key"))
+}
+
+fn secret_key_bytes(sk: &Scalar) -> SecretKeyBls {
+    let mut bytes = sk.to_bytes();
+    bytes.reverse();
+    let sk = SecretKeyBls(bytes.to_vec());
+    bytes.zeroize();
+    sk
+}
+
+fn public_key(pk: &PublicKeyBls) -> Result<G1Affine, CryptoError> {
+    let bytes: &[u8; 48] =
+        pk.0.as_slice()
+            .try_into()
+            .map_err(|_| CryptoError::InvalidPublicKey)?;
+
+    Option::from(G1Affine::from_compressed(bytes)).ok_or(CryptoError::InvalidPublicKey)
+}
+
+fn public_keys<'a>(
+    pks: impl IntoIterator<Item = &'a PublicKeyBls>,
+) -> Result<Vec<G1Affine>, CryptoError> {
+    pks.into_iter().map(public_key).collect()
+}
+
+fn signature(sig: &BlsSignature) -> Result<G2Affine, CryptoError> {
+    let bytes: &[u8; 96] = sig
+        .0
+        .as_slice()
+        .try_into()
+        .map_err(|_| CryptoError::InvalidSignature)?;
+
+    Option::from(G2Affine::from_compressed(bytes)).ok_or(CryptoError::InvalidSignature)
+}
+
+fn signatures(sigs: &[&BlsSignature]) -> Result<Vec<G2Affine>, CryptoError> {
+    sigs.iter().map(|sig| signature(sig)).collect()
+}
+
+fn hash_to_g2(msg: &[u8], dst: &[u8]) -> G2Projective {
+    <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(msg, dst)
+}
+
+// Check that `e(-g1, sig) * Π e(pk_i, H(m_i)) == 1`.
+fn pairing_check(sig: &G2Affine, terms: &[(G1Affine, G2Affine)]) -> bool {
+    let neg_g1 = -G1Affine::generator();
+    let sig = G2Prepared::from(*sig);
+    let terms = terms
+        .iter()
+        .map(|(pk, hash)| (pk, G2Prepared::from(*hash)))
+        .collect::<Vec<_>>();
+
+    let mut pairs = Vec::with_capacity(terms.len() + 1);
+    pairs.push((&neg_g1, &sig));
+    pairs.extend(terms.iter().map(|(pk, hash)| (*pk, hash)));
+
+    multi_miller_loop(&pairs).final_exponentiation() == Gt::identity()
+}
+
+// `KeyGen` of version 4 of the BLS signature draft, with an empty `key_info`.
+pub(super) fn keygen(ikm: &[u8]) -> Result<SecretKeyBls, CryptoError> {
+    if ikm.len() < 32 {
+        return Err(algorithm_error("initial key material is too short"));
+    }
+
+    let mut ikm = [ikm, &[0]].concat();
+    // I2OSP(L, 2), with L = 48 for BLS12-381.
+    let info = [0, 48];
+
+    let mut salt = KEYGEN_SALT.to_vec();
+    loop {
+        salt = Sha256::digest(&salt).to_vec();
+
+        let mut prk = [0; 32];
+        hkdf_extract(cryptoxide::sha2::Sha256::new(), &salt, &ikm, &mut prk);
+        let mut okm = [0; 48];
+        hkdf_expand(cryptoxide::sha2::Sha256::new(), &prk, &info, &mut okm);
+
+        // OS2IP(OKM) mod r
+        let mut wide = [0; 64];
+        wide[..48].copy_from_slice(&okm);
+        wide[..48].reverse();
+        let mut sk = Scalar::from_bytes_wide(&wide);
+        prk.zeroize();
+        okm.zeroize();
+        wide.zeroize();
+
+        if sk != Scalar::zero() {
+            let bytes = secret_key_bytes(&sk);
+            sk.zeroize();
+            ikm.zeroize();
+            return Ok(bytes);
+        }
+    }
+}
+
+pub(super) fn derive_pk(sk: &SecretKeyBls) -> Result<PublicKeyBls, CryptoError> {
+    let mut sk = secret_key(sk)?;
+    let pk = G1Affine::from(G1Affine::generator() * sk);
+    sk.zeroize();
+
+    Ok(PublicKeyBls(pk.to_compressed().to_vec()))
+}
+
+pub(super) fn sign(sk: &SecretKeyBls, msg: &[u8], dst: &[u8]) -> Result<BlsSignature, CryptoError> {
+    let mut sk = secret_key(sk)?;
+    let sig = G2Affine::from(hash_to_g2(msg, dst) * sk);
+    sk.zeroize();
+
+    Ok(BlsSignature(sig.to_compressed().to_vec()))
+}
+
+pub(super) fn verify(
+    sig: &BlsSignature,
+    msg: &[u8],
+    pk: &PublicKeyBls,
+    dst: &[u8],
+) -> Result<bool, CryptoError> {
+    aggregate_verify(sig, &[msg], &[pk], dst)
+}
+
+pub(super) fn aggregate_verify(
+    sig: &BlsSignature,
+    msgs: &[&[u8]],
+    pks: &[&PublicKeyBls],
+    dst: &[u8],
+) -> Result<bool, CryptoError> {
+    let sig = signature(sig)?;
+    let pks = public_keys(pks.iter().copied())?;
+
+    if pks.is_empty() || msgs.len() != pks.len() {
+        return Ok(false);
+    }
+    // As blst, reject the identity as public key.
+    if pks.iter().any(|pk| bool::from(pk.is_identity())) {
+        return Ok(false);
+    }
+
+    let terms = pks
+        .into_iter()
+        .zip(msgs)
+        .map(|(pk, msg)| (pk, G2Affine::from(hash_to_g2(msg, dst))))
+        .collect::<Vec<_>>();
+
+    Ok(pairing_check(&sig, &terms))
+}
+
+pub(super) fn fast_aggregate_verify(
+    sig: &BlsSignature,
+    msg: &[u8],
+    pks: &[PublicKeyBls],
+    dst: &[u8],
+) -> Result<bool, CryptoError> {
+    let sig = signature(sig)?;
+    let pk = aggregate(public_keys(pks)?)?;
+
+    Ok(pairing_check(
+        &sig,
+        &[(pk, G2Affine::from(hash_to_g2(msg, dst)))],
+    ))
+}
+
+pub(super) fn batch_verify(
+    sigs: &[&BlsSignature],
+    msgs: &[&[u8]],
+    pks: &[&PublicKeyBls],
+    weights: &[u128],
+    dst: &[u8],
+) -> Result<bool, CryptoError> {
+    let sigs = signatures(sigs)?;
+    let pks = public_keys(pks.iter().copied())?;
+
+    let n = pks.len();
+    if n == 0 || msgs.len() != n || sigs.len() != n || weights.len() != n {
+        return Ok(false);
+    }
+    if pks.iter().any(|pk| bool::from(pk.is_identity())) {
+        return Ok(false);
+    }
+
+    // Check `Σ w_i * sig_i` against `Σ e(w_i * pk_i, H(m_i))`.
+    let mut sig = G2Projective::identity();
+    let mut terms = Vec::with_capacity(n);
+    for (((pk, s), msg), weight) in pks.iter().zip(&sigs).zip(msgs).zip(weights) {
+        let weight = Scalar::from_raw([*weight as u64, (*weight >> 64) as u64, 0, 0]);
+        sig += s * weight;
+        terms.push((
+            G1Affine::from(pk * weight),
+            G2Affine::from(hash_to_g2(msg, dst)),
+        ));
+    }
+
+    Ok(pairing_check(&G2Affine::from(sig), &terms))
+}
+
+pub(super) fn aggregate_sigs(sigs: &[&BlsSignature]) -> Result<BlsSignature, CryptoError> {
+    let sigs = signatures(sigs)?;
+    if sigs.is_empty() {
+        return Err(algorithm_error("no signature to aggregate"));
+    }
+
+    let sig = sigs
+        .iter()
+        .fold(G2Projective::identity(), |acc, sig| acc + sig);
+
+    Ok(BlsSignature(G2Affine::from(sig).to_compressed().to_vec()))
+}
+
+pub(super) fn aggregate_pks(pks: &[&PublicKeyBls]) -> Result<PublicKeyBls, CryptoError> {
+    let pks = public_keys(pks.iter().copied())?;
+    if pks.iter().any(|pk| bool::from(pk.is_identity())) {
+        return Err(algorithm_error("public key is the identity"));
+    }
+
+    Ok(PublicKeyBls(aggregate(pks)?.to_compressed().to_vec()))
+}
+
+fn aggregate(pks: Vec<G1Affine>) -> Result<G1Affine, CryptoError> {
+    if pks.is_empty() {
+        return Err(algorithm_error("no public key to aggregate"));
+    }
+
+    let pk = pks
+        .iter()
+        .fold(G1Projective::identity(), |acc, pk| acc + pk);
+
+    Ok(G1Affine::from(pk))
+}
+
+#[cfg(all(test, feature = "bls"))]
+mod tests {
+    use super::super::blst;
+    use crate::bls::{AUG_CIPHER_SUITE, POP_CIPHER_SUITE, SIG_POP_CIPHER_SUITE};
+    use crate::hash::{BlsSignature, PublicKeyBls};
+    use proptest::prelude::*;
+
+    proptest! {
+        // Pairings are slow without optimisations.
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn same_keys_and_signatures_as_blst(
+            ikm in any::<[u8; 32]>(),
+            msg in any::<Vec<u8>>(),
+        ) {
+            let sk = super::keygen(&ikm).unwrap();
+            assert_eq!(sk, blst::keygen(&ikm).unwrap());
+
+            let pk = super::derive_pk(&sk).unwrap();
+            assert_eq!(pk, blst::derive_pk(&sk).unwrap());
+
+            for dst in [AUG_CIPHER_SUITE, POP_CIPHER_SUITE, SIG_POP_CIPHER_SUITE] {
+                let sig = super::sign(&sk, &msg, dst.as_bytes()).unwrap();
+                assert_eq!(sig, blst::sign(&sk, &msg, dst.as_bytes()).unwrap());
+
+                assert!(super::verify(&sig, &msg, &pk, dst.as_bytes()).unwrap());
+                let other = [&msg[..], b"other"].concat();
+                assert!(!super::verify(&sig, &other, &pk, dst.as_bytes()).unwrap());
+            }
+        }
+
+        #[test]
+        fn same_aggregates_as_blst(
+            ikms in proptest::collection::vec(any::<[u8; 32]>(), 1..4),
+            msg in any::<Vec<u8>>(),
+        ) {
+            let dst = SIG_POP_CIPHER_SUITE.as_bytes();
+            let keys = ikms
+                .iter()
+                .map(|ikm| {
+                    let sk = blst::keygen(ikm).unwrap();
+                    let pk = blst::derive_pk(&sk).unwrap();
+                    (pk, blst::sign(&sk, &msg, dst).unwrap())
+                })
+                .collect::<Vec<_>>();
+            let pks = keys.iter().map(|(pk, _)| pk).collect::<Vec<_>>();
+            let sigs = keys.iter().map(|(_, sig)| sig).collect::<Vec<_>>();
+
+            let sig = super::aggregate_sigs(&sigs).unwrap();
+            assert_eq!(sig, blst::aggregate_sigs(&sigs).unwrap());
+            let pk = super::aggregate_pks(&pks).unwrap();
+            assert_eq!(pk, blst::aggregate_pks(&pks).unwrap());
+
+            let pks = pks.into_iter().cloned().collect::<Vec<_>>();
+            assert!(super::fast_aggregate_verify(&sig, &msg, &pks, dst).unwrap());
+            assert!(super::fast_aggregate_verify(&sig, &msg, &[pk], dst).unwrap());
+            assert!(!super::fast_aggregate_verify(&sig, b"other", &pks, dst).unwrap());
+        }
+
+        #[test]
+        fn same_verification_as_blst(
+            ikms in proptest::collection::vec(any::<[u8; 32]>(), 1..4),
+            msgs in proptest::collection::vec(any::<Vec<u8>>(), 3),
+            weights in proptest::collection::vec(any::<u128>(), 3),
+            tampered in any::<Option<prop::sample::Index>>(),
+        ) {
+            let dst = AUG_CIPHER_SUITE.as_bytes();
+            let signed = ikms
+                .iter()
+                .zip(&msgs)
+                .map(|(ikm, msg)| {
+                    let sk = blst::keygen(ikm).unwrap();
+                    let pk = blst::derive_pk(&sk).unwrap();
+                    let sig = blst::sign(&sk, msg, dst).unwrap();
+                    (pk, msg.as_slice(), sig)
+                })
+                .collect::<Vec<_>>();
+
+            let mut pks = signed.iter().map(|(pk, _, _)| pk).collect::<Vec<_>>();
+            let msgs = signed.iter().map(|(_, msg, _)| *msg).collect::<Vec<_>>();
+            let sigs = signed.iter().map(|(_, _, sig)| sig).collect::<Vec<_>>();
+            let weights = weights[..signed.len()].iter().map(|w| w | 1).collect::<Vec<_>>();
+
+            // Verifying with the wrong public key must fail in both backends.
+            let other = blst::derive_pk(&blst::keygen(&[42; 32]).unwrap()).unwrap();
+            if let Some(index) = tampered {
+                pks[index.index(signed.len())] = &other;
+            }
+
+            let sig = blst::aggregate_sigs(&sigs).unwrap();
+            let expected = blst::aggregate_verify(&sig, &msgs, &pks, dst).unwrap();
+            assert_eq!(expected, tampered.is_none());
+            assert_eq!(super::aggregate_verify(&sig, &msgs, &pks, dst).unwrap(), expected);
+
+            let expected = blst::batch_verify(&sigs, &msgs, &pks, &weights, dst).unwrap();
+            assert_eq!(expected, tampered.is_none());
+            assert_eq!(
+                super::batch_verify(&sigs, &msgs, &pks, &weights, dst).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn same_edge_cases_as_blst() {
+        let dst = AUG_CIPHER_SUITE.as_bytes();
+        let sk = blst::keygen(&[1; 32]).unwrap();
+        let sig = blst::sign(&sk, b"msg", dst).unwrap();
+
+        // The identity, as a public key.
+        let mut identity = vec![0; 48];
+        identity[0] = 0xc0;
+        let identity = PublicKeyBls(identity);
+        assert!(!blst::verify(&sig, b"msg", &identity, dst).unwrap());
+        assert!(!super::verify(&sig, b"msg", &identity, dst).unwrap());
+        assert!(blst::aggregate_pks(&[&identity]).is_err());
+        assert!(super::aggregate_pks(&[&identity]).is_err());
+
+        assert!(!blst::aggregate_verify(&sig, &[], &[], dst).unwrap());
+        assert!(!super::aggregate_verify(&sig, &[], &[], dst).unwrap());
+        assert!(blst::fast_aggregate_verify(&sig, b"msg", &[], dst).is_err());
+        assert!(super::fast_aggregate_verify(&sig, b"msg", &[], dst).is_err());
+        assert!(blst::aggregate_sigs(&[]).is_err());
+        assert!(super::aggregate_sigs(&[]).is_err());
+
+        let invalid = BlsSignature(vec![0; 96]);
+        assert!(matches!(
+            blst::verify(&invalid, b"msg", &identity, dst),
+            Err(crate::CryptoError::InvalidSignature)
+        ));
+        assert!(matches!(
+            super::verify(&invalid, b"msg", &identity, dst),
+            Err(crate::CryptoError::InvalidSignature)
+        ));
+    }
+}
@@ -0,0 +1,372 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// Ported from octez: lib_signer_backends/encrypted.ml
+//
+// Copyright (c) 2018 Dynamic Ledger Solutions, Inc. <contact@tezos.com>
+// Copyright (c) 2018 Nomadic Labs, <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+//! Secret keys encrypted with a password, as stored by octez-client wallets.
+//!
+//! The encryption key is derived from the password with PBKDF2-HMAC-SHA512,
+//! using a random 8 bytes salt, and the secret key is encrypted with the
+//! NaCl `secretbox` construction (XSalsa20-Poly1305) under a zero nonce.
+//! The encrypted key is the salt followed by the boxed secret key.
+
+use crate::base58::FromBase58CheckError;
+use crate::hash::{
+    EncryptedSecretKeyBls, EncryptedSecretKeyEd25519, EncryptedSecretKeyP256,
+    EncryptedSecretKeySecp256k1, HashTrait, SecretKeyBls, SecretKeyEd25519, SecretKeyP256,
+    SecretKeySecp256k1,
+};
+use crate::secret_key::SecretKey;
+use crate::CryptoError;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use cryptoxide::hmac::Hmac;
+use cryptoxide::mac::{Mac, MacResult};
+use cryptoxide::pbkdf2::pbkdf2;
+use cryptoxide::poly1305::Poly1305;
+use cryptoxide::salsa20::Salsa20;
+use cryptoxide::sha2::Sha512;
+use zeroize::Zeroize;
+
+/// Size of the salt prepended to the encrypted secret key.
+pub const SALT_SIZE: usize = 8;
+
+const PBKDF2_ITERATIONS: u32 = 32768;
+const KEY_SIZE: usize = 32;
+const NONCE_SIZE: usize = 24;
+const NONCE: [u8; NONCE_SIZE] = [0; NONCE_SIZE];
+const TAG_SIZE: usize = 16;
+
+fn derive_key(password: &[u8], salt: &[u8]) -> [u8; KEY_SIZE] {
+    let mut key = [0; KEY_SIZE];
+    let mut mac = Hmac::new(Sha512::new(), password);
+    pbkdf2(&mut mac, salt, PBKDF2_ITERATIONS, &mut key);
+    key
+}
+
+/// Returns the stream cipher, positioned after the Poly1305 one-time key,
+/// and the one-time key itself.
+fn secretbox_init(key: &[u8; KEY_SIZE], nonce: &[u8; NONCE_SIZE]) -> (Salsa20, [u8; 32]) {
+    let mut cipher = Salsa20::new_xsalsa20(key, nonce);
+    let mut mac_key = [0; 32];
+    cipher.process(&[0; 32], &mut mac_key);
+    (cipher, mac_key)
+}
+
+fn secretbox_seal(key: &[u8; KEY_SIZE], nonce: &[u8; NONCE_SIZE], plaintext: &[u8]) -> Vec<u8> {
+    let (mut cipher, mut mac_key) = secretbox_init(key, nonce);
+
+    let mut boxed = vec![0; TAG_SIZE + plaintext.len()];
+    cipher.process(plaintext, &mut boxed[TAG_SIZE..]);
+
+    let mut mac = Poly1305::new(&mac_key);
+    mac.input(&boxed[TAG_SIZE..]);
+    mac.raw_result(&mut boxed[..TAG_SIZE]);
+    mac_key.zeroize();
+
+    boxed
+}
+
+fn secretbox_open(
+    key: &[u8; KEY_SIZE],
+    nonce: &[u8; NONCE_SIZE],
+    boxed: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    if boxed.len() < TAG_SIZE {
+        return Err(CryptoError::FailedToDecrypt);
+    }
+    let (tag, ciphertext) = boxed.split_at(TAG_SIZE);
+    let (mut cipher, mut mac_key) = secretbox_init(key, nonce);
+
+    let mut mac = Poly1305::new(&mac_key);
+    mac.input(ciphertext);
+    let authentic = mac.result() == MacResult::new(tag);
+    mac_key.zeroize();
+    if !authentic {
+        return Err(CryptoError::FailedToDecrypt);
+    }
+
+    let mut plaintext = vec![0; ciphertext.len()];
+    cipher.process(ciphertext, &mut plaintext);
+    Ok(plaintext)
+}
+
+fn encrypt(password: &[u8], salt: &[u8; SALT_SIZE], secret: &[u8]) -> Vec<u8> {
+    let mut key = derive_key(password, salt);
+    let boxed = secretbox_seal(&key, &NONCE, secret);
+    key.zeroize();
+
+    let mut encrypted = Vec::with_capacity(SALT_SIZE + boxed.len());
+    encrypted.extend_from_slice(salt);
+    encrypted.extend_from_slice(&boxed);
+    encrypted
+}
+
+fn decrypt(password: &[u8], encrypted: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if encrypted.len() < SALT_SIZE {
+        return Err(CryptoError::FailedToDecrypt);
+    }
+    let (salt, boxed) = encrypted.split_at(SALT_SIZE);
+
+    let mut key = derive_key(password, salt);
+    let secret = secretbox_open(&key, &NONCE, boxed);
+    key.zeroize();
+    secret
+}
+
+macro_rules! encrypted_sk {
+    ($esk:ident, $sk:ident) => {
+        impl $esk {
+            /// Decrypt the secret key with the given `password`.
+            pub fn decrypt(&self, password: impl AsRef<[u8]>) -> Result<$sk, CryptoError> {
+                let mut secret = decrypt(password.as_ref(), &self.0)?;
+                let sk = $sk::try_from_bytes(&secret).map_err(|_| CryptoError::FailedToDecrypt);
+                secret.zeroize();
+                sk
+            }
+        }
+
+        impl $sk {
+            /// Encrypt the secret key with the given `password` and `salt`.
+            ///
+            /// The salt should be picked at random for every encryption.
+            pub fn encrypt(&self, password: impl AsRef<[u8]>, salt: [u8; SALT_SIZE]) -> $esk {
+                $esk(encrypt(password.as_ref(), &salt, &self.0))
+            }
+        }
+    };
+}
+
+encrypted_sk!(EncryptedSecretKeyEd25519, SecretKeyEd25519);
+encrypted_sk!(EncryptedSecretKeySecp256k1, SecretKeySecp256k1);
+encrypted_sk!(EncryptedSecretKeyP256, SecretKeyP256);
+encrypted_sk!(EncryptedSecretKeyBls, SecretKeyBls);
+
+/// Encrypted secret key of any of the supported curves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncryptedSecretKey {
+    /// Edesk - encrypted secret key (seed) for Ed25519.
+    Ed25519(EncryptedSecretKeyEd25519),
+    /// Spesk - encrypted secret key for Secp256k1.
+    Secp256k1(EncryptedSecretKeySecp256k1),
+    /// P2esk - encrypted secret key for P256.
+    P256(EncryptedSecretKeyP256),
+    /// BLesk - encrypted secret key for BLS.
+    Bls(EncryptedSecretKeyBls),
+}
+
+impl EncryptedSecretKey {
+    pub fn from_base58_check(data: &str) -> Result<Self, FromBase58CheckError> {
+        if data.starts_with("edesk") {
+            Ok(EncryptedSecretKey::Ed25519(
+                EncryptedSecretKeyEd25519::from_b58check(data)?,
+            ))
+        } else if data.starts_with("spesk") {
+            Ok(EncryptedSecretKey::Secp256k1(
+                EncryptedSecretKeySecp256k1::from_b58check(data)?,
+            ))
+        } else if data.starts_with("p2esk") {
+            Ok(EncryptedSecretKey::P256(
+                EncryptedSecretKeyP256::from_b58check(data)?,
+            ))
+        } else if data.starts_with("BLesk") {
+            Ok(EncryptedSecretKey::Bls(
+                EncryptedSecretKeyBls::from_b58check(data)?,
+            ))
+        } else {
+            Err(FromBase58CheckError::IncorrectBase58Prefix)
+        }
+    }
+
+    pub fn to_base58_check(&self) -> String {
+        match self {
+            Self::Ed25519(esk) => esk.to_b58check(),
+            Self::Secp256k1(esk) => esk.to_b58check(),
+            Self::P256(esk) => esk.to_b58check(),
+            Self::Bls(esk) => esk.to_b58check(),
+        }
+    }
+
+    /// Decrypt the secret key with the given `password`.
+    ///
+    /// Fails with [CryptoError::FailedToDecrypt] if the password is wrong.
+    pub fn decrypt(&self, password: impl AsRef<[u8]>) -> Result<SecretKey, CryptoError> {
+        match self {
+            Self::Ed25519(esk) => esk.decrypt(password).map(SecretKey::Ed25519),
+            Self::Secp256k1(esk) => esk.decrypt(password).map(SecretKey::Secp256k1),
+            Self::P256(esk) => esk.decrypt(password).map(SecretKey::P256),
+            Self::Bls(esk) => esk.decrypt(password).map(SecretKey::Bls),
+        }
+    }
+}
+
+impl SecretKey {
+    /// Encrypt the secret key with the given `password` and `salt`.
+    ///
+    /// The salt should be picked at random for every encryption.
+    pub fn encrypt(&self, password: impl AsRef<[u8]>, salt: [u8; SALT_SIZE]) -> EncryptedSecretKey {
+        match self {
+            Self::Ed25519(sk) => EncryptedSecretKey::Ed25519(sk.encrypt(password, salt)),
+            Self::Secp256k1(sk) => EncryptedSecretKey::Secp256k1(sk.encrypt(password, salt)),
+            Self::P256(sk) => EncryptedSecretKey::P256(sk.encrypt(password, salt)),
+            Self::Bls(sk) => EncryptedSecretKey::Bls(sk.encrypt(password, salt)),
+        }
+    }
+}
+
+impl TryFrom<&str> for EncryptedSecretKey {
+    type Error = FromBase58CheckError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        EncryptedSecretKey::from_base58_check(s)
+    }
+}
+
+impl ::core::str::FromStr for EncryptedSecretKey {
+    type Err = FromBase58CheckError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EncryptedSecretKey::from_base58_check(s)
+    }
+}
+
+impl ::core::fmt::Display for EncryptedSecretKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.to_base58_check())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Keys encrypted with password `tezos` and salt `0102030405060708`,
+    // computed independently of this implementation.
+    const KEYS: [(&str, &str); 4] = [
+        (
+            "edesk1GBQ31Tu4Cfg4fFLUeyy5xShGHFwnYigJHn56yvACSAS4ZDfnYzes7g6ZHVHpca9MMkNmoMhbEzgj5L88CR",
+            "edsk3gUfUPyBSfrS9CCgmCiQsTCHGkviBDusMxDJstFtojtc1zcpsh",
+        ),
+        (
+            "spesk1RzvxguVHK8kAhAc2pYSAasbe7HxRqQazWPv4SARWbQuMH6V8B5jYjLX6d9oK2s7d8KssuyB4Z8D3P3p31C",
+            "spsk2SV3aKzS1Q1edSkxLMzTiewSyij7grAvGnsEQMYA1k3xvBT8DF",
+        ),
+        (
+            "p2esk1kpZnyokqFG3k6CBhnaYdnbHKj67n6CQpKzMUmhkAfejcNfLqgA6eUppaSN4TawZ9daZ2R37iTAPC7xCkmj",
+            "p2sk3aH3xfzujyDbCpDuGLSsPt83s6cmn3qVh9HwA3rmDG14HXUpUv",
+        ),
+        (
+            "BLesk1E4obwx5pFsAKaSMdNDmrb6infzLAYHs87P3j8gYbCthWApagYpLaimecB7BoaFJxFRuoEb5jzQ7LKuwJnZ",
+            "BLsk1WTwJFkLU2P57itDq1cgEUqJK7Fwygvtj49vT4HeLfNBXRgpDA",
+        ),
+    ];
+    const SALT: [u8; SALT_SIZE] = [1, 2, 3, 4, 5, 6, 7, 8];
+
+    #[test]
+    fn test_decrypt() {
+        for (esk, sk) in KEYS {
+            let esk = EncryptedSecretKey::from_base58_check(esk).expect("valid encrypted key");
+            let decrypted = esk.decrypt("tezos").expect("correct password");
+            assert_eq!(decrypted.to_base58_check(), sk);
+        }
+    }
+
+    #[test]
+    fn test_encrypt() {
+        for (esk, sk) in KEYS {
+            let sk = SecretKey::from_base58_check(sk).unwrap();
+            assert_eq!(sk.encrypt("tezos", SALT).to_base58_check(), esk);
+        }
+    }
+
+    #[test]
+    fn test_wrong_password() {
+        for (esk, _) in KEYS {
+            let esk: EncryptedSecretKey = esk.parse().unwrap();
+            assert!(matches!(
+                esk.decrypt("wrong"),
+                Err(CryptoError::FailedToDecrypt)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_wallet_secret_keys() {
+        // Layout of the `secret_keys` file of an octez-client wallet.
+        let wallet = r#"[
+            { "name": "alice",
+              "value": "encrypted:edesk1GBQ31Tu4Cfg4fFLUeyy5xShGHFwnYigJHn56yvACSAS4ZDfnYzes7g6ZHVHpca9MMkNmoMhbEzgj5L88CR" },
+            { "name": "bob",
+              "value": "unencrypted:spsk2SV3aKzS1Q1edSkxLMzTiewSyij7grAvGnsEQMYA1k3xvBT8DF" } ]"#;
+        let wallet: Vec<serde_json::Value> = serde_json::from_str(wallet).unwrap();
+
+        let value = |i: usize| wallet[i]["value"].as_str().unwrap();
+        let alice = value(0)
+            .strip_prefix("encrypted:")
+            .map(EncryptedSecretKey::from_base58_check)
+            .unwrap()
+            .unwrap()
+            .decrypt("tezos")
+            .unwrap();
+        assert_eq!(
+            alice.to_base58_check(),
+            "edsk3gUfUPyBSfrS9CCgmCiQsTCHGkviBDusMxDJstFtojtc1zcpsh"
+        );
+
+        assert!(EncryptedSecretKey::from_base58_check(
+            value(1).strip_prefix("unencrypted:").unwrap()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_secretbox() {
+        // Test vector from the NaCl distribution (tests/secretbox.c).
+        let key: [u8; KEY_SIZE] =
+            hex::decode("1b27556473e985d462cd51197a9a46c76009549eac6474f206c4ee0844f68389")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let plaintext = hex::decode(
+            "be075fc53c81f2d5cf141316ebeb0c7b5228c52a4c62cbd44b66849b64244ffce5ecbaaf33bd751a\
+             1ac728d45e6c61296cdc3c01233561f41db66cce314adb310e3be8250c46f06dceea3a7fa134805\
+             7e2f6556ad6b1318a024a838f21af1fde048977eb48f59ffd4924ca1c60902e52f0a089bc768970\
+             40e082f937763848645e0705",
+        )
+        .unwrap();
+        let nonce: [u8; NONCE_SIZE] =
+            hex::decode("69696ee955b62b73cd62bda875fc73d68219e0036b7a0b37")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let expected = hex::decode(
+            "f3ffc7703f9400e52a7dfb4b3d3305d98e993b9f48681273c29650ba32fc76ce48332ea7164d96a4\
+             476fb8c531a1186ac0dfc17c98dce87b4da7f011ec48c97271d2c20f9b928fe2270d6fb863d5173\
+             8b48eeee314a7cc8ab932164548e526ae90224368517acfeabd6bb3732bc0e9da99832b61ca01b6\
+             de56244a9e88d5f9b37973f622a43d14a6599b1f654cb45a74e355a5",
+        )
+        .unwrap();
+
+        assert_eq!(secretbox_seal(&key, &nonce, &plaintext), expected);
+        assert_eq!(secretbox_open(&key, &nonce, &expected).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_secretbox_round_trip() {
+        let key = [42; KEY_SIZE];
+        let boxed = secretbox_seal(&key, &NONCE, b"secret");
+        assert_eq!(secretbox_open(&key, &NONCE, &boxed).unwrap(), b"secret");
+
+        let mut tampered = boxed.clone();
+        tampered[TAG_SIZE] ^= 1;
+        assert!(matches!(
+            secretbox_open(&key, &NONCE, &tampered),
+            Err(CryptoError::FailedToDecrypt)
+        ));
+    }
+}
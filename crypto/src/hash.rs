@@ -2,11 +2,15 @@
 // SPDX-CopyrightText: 2022-2024 Trilitech <contact@trili.tech>
 // SPDX-License-Identifier: MIT
 
-use std::convert::{TryFrom, TryInto};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::{TryFrom, TryInto};
 
 use crate::{
     base58::{FromBase58Check, FromBase58CheckError, ToBase58Check},
     blake2b::{self, Blake2bError},
+    merkle::{MerkleProof, MerkleTree},
     CryptoError, PublicKeySignatureVerifier, PublicKeyWithHash,
 };
 use serde::{Deserialize, Serialize};
@@ -40,7 +44,14 @@ mod prefix_bytes {
     // SecretKeyEd25519 uses identical b58 encoding as SeedEd25519 in
     // non-legacy format.
     pub const SECRET_KEY_ED25519: [u8; 4] = SEED_ED25519;
+    pub const LEGACY_SECRET_KEY_ED25519: [u8; 4] = [43, 246, 78, 7];
+    pub const SECRET_KEY_SECP256K1: [u8; 4] = [17, 162, 224, 201];
+    pub const SECRET_KEY_P256: [u8; 4] = [16, 81, 238, 189];
     pub const SECRET_KEY_BLS: [u8; 4] = [3, 150, 192, 40];
+    pub const ENCRYPTED_SECRET_KEY_ED25519: [u8; 5] = [7, 90, 60, 179, 41];
+    pub const ENCRYPTED_SECRET_KEY_SECP256K1: [u8; 5] = [9, 237, 241, 174, 150];
+    pub const ENCRYPTED_SECRET_KEY_P256: [u8; 5] = [9, 48, 57, 115, 171];
+    pub const ENCRYPTED_SECRET_KEY_BLS: [u8; 5] = [2, 5, 30, 53, 25];
     pub const GENERIC_SIGNATURE_HASH: [u8; 3] = [4, 130, 43];
     pub const ED22519_SIGNATURE_HASH: [u8; 5] = [9, 245, 205, 134, 18];
     pub const SECP256K1_SIGNATURE_HASH: [u8; 5] = [13, 115, 101, 19, 63];
@@ -113,8 +124,8 @@ macro_rules! define_hash {
             }
         }
 
-        impl ::std::fmt::Display for $name {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 // TODO - TE-373: with b58 this could be done without the need
                 // to perform a heap allocation.
                 write!(f, "{}", self.to_base58_check())
@@ -132,8 +143,8 @@ macro_rules! define_hash {
             }
         }
 
-        impl ::std::fmt::Debug for $name {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 // TODO - TE-373: with b58 this could be done without the need
                 // to perform a heap allocation.
                 f.debug_tuple(stringify!($name))
@@ -142,7 +153,7 @@ macro_rules! define_hash {
             }
         }
 
-        impl std::str::FromStr for $name {
+        impl core::str::FromStr for $name {
             type Err = FromBase58CheckError;
 
             fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -173,33 +184,33 @@ macro_rules! define_hash {
             }
         }
 
-        impl std::convert::AsRef<[u8]> for $name {
+        impl core::convert::AsRef<[u8]> for $name {
             fn as_ref(&self) -> &[u8] {
                 &self.0
             }
         }
 
-        impl std::convert::From<$name> for Hash {
+        impl core::convert::From<$name> for Hash {
             fn from(typed_hash: $name) -> Self {
                 typed_hash.0
             }
         }
 
-        impl std::convert::TryFrom<&[u8]> for $name {
+        impl core::convert::TryFrom<&[u8]> for $name {
             type Error = FromBytesError;
             fn try_from(h: &[u8]) -> Result<Self, Self::Error> {
                 Self::from_bytes(h)
             }
         }
 
-        impl std::convert::TryFrom<Hash> for $name {
+        impl core::convert::TryFrom<Hash> for $name {
             type Error = FromBytesError;
             fn try_from(h: Hash) -> Result<Self, Self::Error> {
                 Self::from_vec(h)
             }
         }
 
-        impl std::convert::TryFrom<&str> for $name {
+        impl core::convert::TryFrom<&str> for $name {
             type Error = FromBase58CheckError;
             fn try_from(encoded: &str) -> Result<Self, Self::Error> {
                 Self::from_base58_check(encoded)
@@ -229,7 +240,7 @@ macro_rules! define_hash {
                 impl<'de> serde::de::Visitor<'de> for HashVisitor {
                     type Value = $name;
 
-                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
                         formatter
                             .write_str("eigher sequence of bytes or base58 encoded data expected")
                     }
@@ -309,7 +320,14 @@ define_hash!(PublicKeyP256);
 define_hash!(PublicKeyBls);
 define_hash!(SeedEd25519);
 define_hash!(SecretKeyEd25519);
+define_hash!(LegacySecretKeyEd25519);
+define_hash!(SecretKeySecp256k1);
+define_hash!(SecretKeyP256);
 define_hash!(SecretKeyBls);
+define_hash!(EncryptedSecretKeyEd25519);
+define_hash!(EncryptedSecretKeySecp256k1);
+define_hash!(EncryptedSecretKeyP256);
+define_hash!(EncryptedSecretKeyBls);
 define_hash!(UnknownSignature);
 define_hash!(Ed25519Signature);
 define_hash!(Secp256k1Signature);
@@ -385,8 +403,22 @@ pub enum HashType {
     SeedEd25519,
     // "\013\015\058\007" (* edsk(54) *)
     SecretKeyEd25519,
+    // "\043\246\078\007" (* edsk(98) *)
+    LegacySecretKeyEd25519,
+    // "\017\162\224\201" (* spsk(54) *)
+    SecretKeySecp256k1,
+    // "\016\081\238\189" (* p2sk(54) *)
+    SecretKeyP256,
     // "\003\150\192\040" (* BLsk(54) *)
     SecretKeyBls,
+    // "\007\090\060\179\041" (* edesk(88) *)
+    EncryptedSecretKeyEd25519,
+    // "\009\237\241\174\150" (* spesk(88) *)
+    EncryptedSecretKeySecp256k1,
+    // "\009\048\057\115\171" (* p2esk(88) *)
+    EncryptedSecretKeyP256,
+    // "\002\005\030\053\025" (* BLesk(88) *)
+    EncryptedSecretKeyBls,
     // "\004\130\043" (* sig(96) *)
     UnknownSignature,
     // "\009\245\205\134\018" (* edsig(99) *)
@@ -432,7 +464,14 @@ impl HashType {
             HashType::PublicKeyBls => &PUBLIC_KEY_BLS,
             HashType::SeedEd25519 => &SEED_ED25519,
             HashType::SecretKeyEd25519 => &SECRET_KEY_ED25519,
+            HashType::LegacySecretKeyEd25519 => &LEGACY_SECRET_KEY_ED25519,
+            HashType::SecretKeySecp256k1 => &SECRET_KEY_SECP256K1,
+            HashType::SecretKeyP256 => &SECRET_KEY_P256,
             HashType::SecretKeyBls => &SECRET_KEY_BLS,
+            HashType::EncryptedSecretKeyEd25519 => &ENCRYPTED_SECRET_KEY_ED25519,
+            HashType::EncryptedSecretKeySecp256k1 => &ENCRYPTED_SECRET_KEY_SECP256K1,
+            HashType::EncryptedSecretKeyP256 => &ENCRYPTED_SECRET_KEY_P256,
+            HashType::EncryptedSecretKeyBls => &ENCRYPTED_SECRET_KEY_BLS,
             HashType::UnknownSignature => &GENERIC_SIGNATURE_HASH,
             HashType::Ed25519Signature => &ED22519_SIGNATURE_HASH,
             HashType::Secp256k1Signature => &SECP256K1_SIGNATURE_HASH,
@@ -468,12 +507,21 @@ impl HashType {
             | HashType::ContractTz4Hash
             | HashType::SmartRollupHash => 20,
             HashType::PublicKeySecp256k1 | HashType::PublicKeyP256 => 33,
-            HashType::SecretKeyEd25519 | HashType::SeedEd25519 | HashType::SecretKeyBls => 32,
+            HashType::SecretKeyEd25519
+            | HashType::SeedEd25519
+            | HashType::SecretKeySecp256k1
+            | HashType::SecretKeyP256
+            | HashType::SecretKeyBls => 32,
             HashType::PublicKeyBls => 48,
+            HashType::EncryptedSecretKeyEd25519
+            | HashType::EncryptedSecretKeySecp256k1
+            | HashType::EncryptedSecretKeyP256
+            | HashType::EncryptedSecretKeyBls => 56,
             HashType::Ed25519Signature
             | HashType::Secp256k1Signature
             | HashType::P256Signature
-            | HashType::UnknownSignature => 64,
+            | HashType::UnknownSignature
+            | HashType::LegacySecretKeyEd25519 => 64,
             HashType::BlsSignature => 96,
         }
     }
@@ -567,7 +615,7 @@ impl SeedEd25519 {
         use ed25519_dalek::{SecretKey, SigningKey};
 
         let mut v = self.0;
-        let secret_key: SecretKey =
+        let mut secret_key: SecretKey =
             v.as_slice()
                 .try_into()
                 .map_err(|_| CryptoError::InvalidKeySize {
@@ -576,6 +624,7 @@ impl SeedEd25519 {
                 })?;
         v.zeroize();
         let sk = SigningKey::from_bytes(&secret_key);
+        secret_key.zeroize();
         let pk = sk.verifying_key();
         Ok((
             PublicKeyEd25519(pk.to_bytes().to_vec()),
@@ -601,22 +650,30 @@ impl PublicKeyEd25519 {
 }
 
 impl SecretKeyEd25519 {
+    fn to_signing_key(&self) -> Result<ed25519_dalek::SigningKey, CryptoError> {
+        self.0
+            .as_slice()
+            .try_into()
+            .map(ed25519_dalek::SigningKey::from_bytes)
+            .map_err(|_| CryptoError::InvalidKeySize {
+                expected: ed25519_dalek::SECRET_KEY_LENGTH,
+                actual: self.0.len(),
+            })
+    }
+
+    /// Derive the public key for the current secret key.
+    pub fn derive_pk(&self) -> Result<PublicKeyEd25519, CryptoError> {
+        let sk = self.to_signing_key()?;
+        Ok(PublicKeyEd25519(sk.verifying_key().to_bytes().to_vec()))
+    }
+
     pub fn sign<I>(&self, data: I) -> Result<Ed25519Signature, CryptoError>
     where
         I: AsRef<[u8]>,
     {
         use ed25519_dalek::Signer;
-        use ed25519_dalek::SigningKey;
 
-        let sk = self
-            .0
-            .as_slice()
-            .try_into()
-            .map(SigningKey::from_bytes)
-            .map_err(|_| CryptoError::InvalidKeySize {
-                expected: ed25519_dalek::SECRET_KEY_LENGTH,
-                actual: self.0.len(),
-            })?;
+        let sk = self.to_signing_key()?;
 
         let payload = crate::blake2b::digest_256(data.as_ref());
         let signature = sk.sign(&payload);
@@ -624,6 +681,175 @@ impl SecretKeyEd25519 {
     }
 }
 
+// The 64-byte form of an ed25519 secret key is made of the seed followed by
+// the public key, as used by older wallets and hardware wallet exports.
+impl TryFrom<&LegacySecretKeyEd25519> for SecretKeyEd25519 {
+    type Error = CryptoError;
+
+    /// Extracts the seed, checking that the embedded public key matches it.
+    fn try_from(source: &LegacySecretKeyEd25519) -> Result<Self, Self::Error> {
+        let (seed, pk) = source.0.split_at(CRYPTO_KEY_SIZE);
+        let sk = SecretKeyEd25519(seed.to_vec());
+        if sk.derive_pk()?.0 != pk {
+            return Err(CryptoError::InvalidKey {
+                reason: "public key does not match the seed".to_string(),
+            });
+        }
+        Ok(sk)
+    }
+}
+
+impl TryFrom<&SecretKeyEd25519> for LegacySecretKeyEd25519 {
+    type Error = CryptoError;
+
+    fn try_from(source: &SecretKeyEd25519) -> Result<Self, Self::Error> {
+        let pk = source.derive_pk()?;
+        let mut bytes = Vec::with_capacity(HashType::LegacySecretKeyEd25519.size());
+        bytes.extend_from_slice(&source.0);
+        bytes.extend_from_slice(&pk.0);
+        Ok(LegacySecretKeyEd25519(bytes))
+    }
+}
+
+/// libsecp256k1 secret key, overwritten when dropped as the library does not
+/// zeroize its keys.
+struct Secp256k1SigningKey(libsecp256k1::SecretKey);
+
+impl Drop for Secp256k1SigningKey {
+    fn drop(&mut self) {
+        self.0 = libsecp256k1::SecretKey::default();
+        core::hint::black_box(&self.0);
+    }
+}
+
+impl SecretKeySecp256k1 {
+    fn to_libsecp256k1(&self) -> Result<Secp256k1SigningKey, CryptoError> {
+        libsecp256k1::SecretKey::parse_slice(&self.0)
+            .map(Secp256k1SigningKey)
+            .map_err(|e| CryptoError::InvalidKey {
+                reason: e.to_string(),
+            })
+    }
+
+    /// Derive the (compressed) public key for the current secret key.
+    pub fn derive_pk(&self) -> Result<PublicKeySecp256k1, CryptoError> {
+        let sk = self.to_libsecp256k1()?;
+        let pk = libsecp256k1::PublicKey::from_secret_key(&sk.0);
+        Ok(PublicKeySecp256k1(pk.serialize_compressed().to_vec()))
+    }
+
+    /// Sign the Blake2b-256 digest of `data`.
+    ///
+    /// The nonce is derived deterministically (RFC 6979) and the signature is
+    /// normalized to its low-S form, as done by octez.
+    pub fn sign<I>(&self, data: I) -> Result<Secp256k1Signature, CryptoError>
+    where
+        I: AsRef<[u8]>,
+    {
+        let sk = self.to_libsecp256k1()?;
+
+        let payload: [u8; 32] = crate::blake2b::digest_256(data.as_ref())
+            .try_into()
+            .map_err(|_| CryptoError::InvalidMessage)?;
+        let msg = libsecp256k1::Message::parse(&payload);
+
+        let (mut signature, _) = libsecp256k1::sign(&msg, &sk.0);
+        signature.normalize_s();
+        Ok(Secp256k1Signature(signature.serialize().to_vec()))
+    }
+}
+
+impl SecretKeyP256 {
+    fn to_signing_key(&self) -> Result<p256::ecdsa::SigningKey, CryptoError> {
+        p256::ecdsa::SigningKey::from_bytes(&self.0).map_err(|e| CryptoError::InvalidKey {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Derive the (compressed) public key for the current secret key.
+    pub fn derive_pk(&self) -> Result<PublicKeyP256, CryptoError> {
+        let sk = self.to_signing_key()?;
+        let pk = sk.verifying_key().to_encoded_point(true);
+        Ok(PublicKeyP256(pk.as_bytes().to_vec()))
+    }
+
+    /// Sign the Blake2b-256 digest of `data`.
+    ///
+    /// The nonce is derived deterministically (RFC 6979, with HMAC-SHA256).
+    pub fn sign<I>(&self, data: I) -> Result<P256Signature, CryptoError>
+    where
+        I: AsRef<[u8]>,
+    {
+        use p256::ecdsa::signature::{
+            digest::{BlockInput, FixedOutput, Reset, Update},
+            DigestSigner,
+        };
+        use p256::elliptic_curve::generic_array::GenericArray;
+
+        // The p256 crate derives the RFC 6979 nonce using the same digest type
+        // both for hashing the message and as the HMAC hash function.
+        // Here the message is already hashed with Blake2b, so this digest
+        // yields the precomputed hash when finalized, but otherwise behaves as
+        // plain SHA256 (which is what the fresh HMAC instances see).
+        #[derive(Default, Clone)]
+        struct Prehashed {
+            sha256: sha2::Sha256,
+            hash: Option<[u8; CRYPTO_KEY_SIZE]>,
+        }
+
+        impl Update for Prehashed {
+            fn update(&mut self, data: impl AsRef<[u8]>) {
+                self.sha256.update(data);
+            }
+        }
+
+        impl BlockInput for Prehashed {
+            type BlockSize = <sha2::Sha256 as BlockInput>::BlockSize;
+        }
+
+        impl FixedOutput for Prehashed {
+            type OutputSize = <sha2::Sha256 as FixedOutput>::OutputSize;
+
+            fn finalize_into(self, out: &mut GenericArray<u8, Self::OutputSize>) {
+                match self.hash {
+                    Some(hash) => out.copy_from_slice(&hash),
+                    None => self.sha256.finalize_into(out),
+                }
+            }
+
+            fn finalize_into_reset(&mut self, out: &mut GenericArray<u8, Self::OutputSize>) {
+                match self.hash.take() {
+                    Some(hash) => out.copy_from_slice(&hash),
+                    None => self.sha256.finalize_into_reset(out),
+                }
+                self.reset();
+            }
+        }
+
+        impl Reset for Prehashed {
+            fn reset(&mut self) {
+                self.sha256.reset();
+                self.hash = None;
+            }
+        }
+
+        let sk = self.to_signing_key()?;
+
+        let payload: [u8; CRYPTO_KEY_SIZE] = crate::blake2b::digest_256(data.as_ref())
+            .try_into()
+            .map_err(|_| CryptoError::InvalidMessage)?;
+        let digest = Prehashed {
+            hash: Some(payload),
+            ..Default::default()
+        };
+
+        let signature: p256::ecdsa::Signature = sk
+            .try_sign_digest(digest)
+            .map_err(|e| CryptoError::AlgorithmError(e.to_string()))?;
+        Ok(P256Signature(signature.as_ref().to_vec()))
+    }
+}
+
 impl PublicKeySignatureVerifier for PublicKeyEd25519 {
     type Signature = Ed25519Signature;
     type Error = CryptoError;
@@ -703,7 +929,7 @@ impl PublicKeySignatureVerifier for PublicKeyP256 {
         impl Update for NoHash {
             fn update(&mut self, data: impl AsRef<[u8]>) {
                 let data = data.as_ref();
-                let end = std::cmp::min(data.len(), self.0.len());
+                let end = core::cmp::min(data.len(), self.0.len());
                 self.0[..end].copy_from_slice(&data[..end]);
             }
         }
@@ -747,7 +973,7 @@ impl PublicKeySignatureVerifier for PublicKeyP256 {
     }
 }
 
-#[cfg(feature = "bls")]
+#[cfg(any(feature = "bls", feature = "bls-rust"))]
 impl PublicKeySignatureVerifier for PublicKeyBls {
     type Signature = BlsSignature;
     type Error = CryptoError;
@@ -766,6 +992,52 @@ impl OperationListHash {
     pub fn calculate(list: &[OperationHash]) -> Self {
         OperationListHash(blake2b::merkle_tree(list))
     }
+
+    /// Proof that the operation at `index` of `list` is included in the hash
+    /// of `list`, or `None` if `index` is out of bounds.
+    pub fn inclusion_proof(list: &[OperationHash], index: usize) -> Option<MerkleProof> {
+        MerkleTree::new(list).proof(index)
+    }
+
+    /// Verifies that `operation` is included in the list of `len` operations this is the hash of.
+    ///
+    /// As the tree is padded with the last operation, proofs for indexes past
+    /// the end of the list would otherwise verify; they are rejected.
+    pub fn verify_inclusion(
+        &self,
+        operation: &OperationHash,
+        len: usize,
+        proof: &MerkleProof,
+    ) -> bool {
+        proof.index() < len && proof.verify(operation.as_ref(), self.as_ref())
+    }
+}
+
+impl OperationListListHash {
+    /// Hash of the lists of operations of a block, given the hash of each list.
+    pub fn calculate(list: &[OperationListHash]) -> Self {
+        OperationListListHash(blake2b::merkle_tree(list))
+    }
+
+    /// Hash of the lists of operations of a block, one list per validation pass.
+    pub fn calculate_from_operations<L>(operations: &[L]) -> Self
+    where
+        L: AsRef<[OperationHash]>,
+    {
+        let list: Vec<_> = operations
+            .iter()
+            .map(|operations| OperationListHash::calculate(operations.as_ref()))
+            .collect();
+        Self::calculate(&list)
+    }
+}
+
+impl BlockHash {
+    /// Hash of a block, given its binary encoded header: the shell header
+    /// followed by the protocol data.
+    pub fn calculate(header: &[u8]) -> Self {
+        BlockHash(blake2b::digest_256(header))
+    }
 }
 
 impl BlockPayloadHash {
@@ -1120,6 +1392,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ed25519_legacy_secret_key() {
+        let legacy = LegacySecretKeyEd25519::from_base58_check(
+            "edskRuR1azSfboG86YPTyxrQgosh5zChf5bVDmptqLTb5EuXAm9rsnDYfTKhq7rDQujdn5WWzwUMeV3agaZ6J2vPQT58jJAJPi",
+        )
+        .unwrap();
+        let sk = SecretKeyEd25519::try_from(&legacy).unwrap();
+        assert_eq!(
+            sk.to_base58_check(),
+            "edsk3gUfUPyBSfrS9CCgmCiQsTCHGkviBDusMxDJstFtojtc1zcpsh"
+        );
+        assert_eq!(LegacySecretKeyEd25519::try_from(&sk).unwrap(), legacy);
+
+        // Same seed, with the public key of another account.
+        let mismatched = LegacySecretKeyEd25519::from_base58_check(
+            "edskRuR1azSfboG86YPTyxrQgosh5zChf5bVDmptqLTb5EuXAkM2hhi4UW9XotHz89GgWDtfvDme95WjHR9HszPAVguv8hhvRR",
+        )
+        .unwrap();
+        assert!(matches!(
+            SecretKeyEd25519::try_from(&mismatched),
+            Err(CryptoError::InvalidKey { .. })
+        ));
+    }
+
     #[test]
     fn test_secp256k1_signature_verification() {
         let pk = PublicKeySecp256k1::from_base58_check(
@@ -1134,6 +1430,53 @@ mod tests {
         assert!(result);
     }
 
+    #[test]
+    fn test_secp256k1_sign() {
+        let sk = SecretKeySecp256k1::from_base58_check(
+            "spsk2SV3aKzS1Q1edSkxLMzTiewSyij7grAvGnsEQMYA1k3xvBT8DF",
+        )
+        .unwrap();
+        let pk = sk.derive_pk().unwrap();
+        assert_eq!(
+            "sppk7bgK42i4MqXdSToddBHCmVgUvQqgkLvpgpocGnGPtGYhi4FS2DQ",
+            pk.to_base58_check()
+        );
+        assert_eq!(
+            "tz2TRpDZTo2uWH1BuiQD58pmywTmD1kt6NWt",
+            pk.pk_hash().to_base58_check()
+        );
+
+        let msg =
+            hex::decode("0300000000000000000000000000000000000000000000000000000000000000006c00")
+                .unwrap();
+        let sig = sk.sign(&msg).unwrap();
+        assert_eq!(
+            "spsig1eSuSkcF4eqLFhJgsEkvyjmAAtz68aNtGo89KhbtRPMBNPZAaFbNkbdPoe5mJWkQxq6n1vNL6UvNMFUX7rPa1wrPey7SsZ",
+            sig.to_base58_check()
+        );
+
+        let payload = crate::blake2b::digest_256(&msg);
+        assert!(pk.verify_signature(&sig, &payload).unwrap());
+    }
+
+    proptest! {
+        #[test]
+        fn test_secp256k1_signature_verification_roundtrip(sk in any::<[u8; 32]>(), message in any::<Vec<u8>>()) {
+            // Out of range scalars are rejected when parsing the key
+            prop_assume!(libsecp256k1::SecretKey::parse(&sk).is_ok());
+            let sk = super::SecretKeySecp256k1(sk.to_vec());
+            let pk = sk.derive_pk().unwrap();
+
+            let sig = sk.sign(&message).unwrap();
+            let parsed = libsecp256k1::Signature::parse_standard_slice(sig.as_ref()).unwrap();
+            assert!(!parsed.s.is_high());
+
+            let payload = crate::blake2b::digest_256(&message);
+            let result = pk.verify_signature(&sig, &payload).unwrap();
+            assert!(result);
+        }
+    }
+
     #[test]
     fn test_p256_signature_verification() {
         let pk = PublicKeyP256::from_base58_check(
@@ -1150,6 +1493,54 @@ mod tests {
         assert!(result);
     }
 
+    // Expected values computed independently with pyca/cryptography
+    // (RFC 6979 nonces, HMAC-SHA256).
+    #[test]
+    fn test_p256_sign() {
+        let sk = SecretKeyP256::from_base58_check(
+            "p2sk3aH3xfzujyDbCpDuGLSsPt83s6cmn3qVh9HwA3rmDG14HXUpUv",
+        )
+        .unwrap();
+        let pk = sk.derive_pk().unwrap();
+        assert_eq!(
+            "p2pk653ADWvBsiKHXJEGx5VbsQzVHvjTfwfJoy5fKCALiQQK9q91oLk",
+            pk.to_base58_check()
+        );
+        assert_eq!(
+            "tz3V4z2s9854NWrWqX7ndmAUB558AdDB8XMw",
+            pk.pk_hash().to_base58_check()
+        );
+
+        let msg =
+            hex::decode("0300000000000000000000000000000000000000000000000000000000000000006c00")
+                .unwrap();
+        let sig = sk.sign(&msg).unwrap();
+        assert_eq!(
+            "p2sigjp6BaZJRhRRhoiB3GPQiE6ThCg7fk9XJpeS2DY5MVU2s7sFJ6Aj1W4LpXWV4odE4rmb4sHH5ZWF7sz9u7sv9VqnMxqXzu",
+            sig.to_base58_check()
+        );
+
+        let payload = crate::blake2b::digest_256(&msg);
+        assert!(pk.verify_signature(&sig, &payload).unwrap());
+    }
+
+    proptest! {
+        #[test]
+        fn test_p256_signature_verification_roundtrip(sk in any::<[u8; 32]>(), message in any::<Vec<u8>>()) {
+            // Out of range scalars are rejected when parsing the key
+            prop_assume!(p256::ecdsa::SigningKey::from_bytes(&sk).is_ok());
+            let sk = super::SecretKeyP256(sk.to_vec());
+            let pk = sk.derive_pk().unwrap();
+
+            let sig = sk.sign(&message).unwrap();
+            assert_eq!(sig, sk.sign(&message).unwrap());
+
+            let payload = crate::blake2b::digest_256(&message);
+            let result = pk.verify_signature(&sig, &payload).unwrap();
+            assert!(result);
+        }
+    }
+
     mod hash_as_json_is_base58check {
         use super::super::*;
 
@@ -1251,6 +1642,24 @@ mod tests {
             ]
         );
 
+        test!(
+            legacy_sk_ed25519,
+            LegacySecretKeyEd25519,
+            ["edskRuR1azSfboG86YPTyxrQgosh5zChf5bVDmptqLTb5EuXAm9rsnDYfTKhq7rDQujdn5WWzwUMeV3agaZ6J2vPQT58jJAJPi"]
+        );
+
+        test!(
+            sk_secp256k1,
+            SecretKeySecp256k1,
+            ["spsk2SV3aKzS1Q1edSkxLMzTiewSyij7grAvGnsEQMYA1k3xvBT8DF"]
+        );
+
+        test!(
+            sk_p256,
+            SecretKeyP256,
+            ["p2sk3aH3xfzujyDbCpDuGLSsPt83s6cmn3qVh9HwA3rmDG14HXUpUv"]
+        );
+
         test!(pk_hash, CryptoboxPublicKeyHash, []);
 
         test!(pk_ed25519, PublicKeyEd25519, []);
@@ -1271,6 +1680,30 @@ mod tests {
             ["BLsk1WTwJFkLU2P57itDq1cgEUqJK7Fwygvtj49vT4HeLfNBXRgpDA"]
         );
 
+        test!(
+            esk_ed25519,
+            EncryptedSecretKeyEd25519,
+            ["edesk1GBQ31Tu4Cfg4fFLUeyy5xShGHFwnYigJHn56yvACSAS4ZDfnYzes7g6ZHVHpca9MMkNmoMhbEzgj5L88CR"]
+        );
+
+        test!(
+            esk_secp256k1,
+            EncryptedSecretKeySecp256k1,
+            ["spesk1RzvxguVHK8kAhAc2pYSAasbe7HxRqQazWPv4SARWbQuMH6V8B5jYjLX6d9oK2s7d8KssuyB4Z8D3P3p31C"]
+        );
+
+        test!(
+            esk_p256,
+            EncryptedSecretKeyP256,
+            ["p2esk1kpZnyokqFG3k6CBhnaYdnbHKj67n6CQpKzMUmhkAfejcNfLqgA6eUppaSN4TawZ9daZ2R37iTAPC7xCkmj"]
+        );
+
+        test!(
+            esk_bls,
+            EncryptedSecretKeyBls,
+            ["BLesk1E4obwx5pFsAKaSMdNDmrb6infzLAYHs87P3j8gYbCthWApagYpLaimecB7BoaFJxFRuoEb5jzQ7LKuwJnZ"]
+        );
+
         test!(
             sig_bls,
             BlsSignature,
@@ -1354,4 +1787,101 @@ mod tests {
             "vh3Ed4mvDcNYVtskGLCYKKk1aBxJTpQNc46Hyi4EedpGCmgZ4LiG",
         );
     }
+
+    #[test]
+    fn operation_list_hash_inclusion() {
+        let operations: Vec<_> = [
+            "oom9d3PpjjaMzgg9mZ1pDrF8kjdyzDb41Bd2XE6Y3kRtFHXLku3",
+            "oojiRXrrXHgukj8Q7d2AV8QCmJHTM4qpxhZqqbAnuG1RHtnbvim",
+            "oo3h4gpQBjXaL63GDSiK54mP7sLydhgDQwBfjBWDhGt1gAcnGfA",
+        ]
+        .into_iter()
+        .map(|op| OperationHash::from_base58_check(op).unwrap())
+        .collect();
+        let operation_list_hash = OperationListHash::calculate(&operations);
+
+        for (index, operation) in operations.iter().enumerate() {
+            let proof = OperationListHash::inclusion_proof(&operations, index).unwrap();
+            assert!(operation_list_hash.verify_inclusion(operation, operations.len(), &proof));
+
+            let other = &operations[(index + 1) % operations.len()];
+            assert!(!operation_list_hash.verify_inclusion(other, operations.len(), &proof));
+        }
+        assert_eq!(OperationListHash::inclusion_proof(&operations, 3), None);
+
+        // The padding repeats the last operation at index 3.
+        let last = &operations[2];
+        let proof = OperationListHash::inclusion_proof(&operations, 2).unwrap();
+        let padded: MerkleProof = serde_json::from_value(serde_json::json!({
+            "index": 3,
+            "path": proof.path(),
+        }))
+        .unwrap();
+        assert!(padded.verify(last.as_ref(), operation_list_hash.as_ref()));
+        assert!(!operation_list_hash.verify_inclusion(last, operations.len(), &padded));
+    }
+
+    #[test]
+    fn operation_list_list_hash() {
+        // Operations hash of the genesis block, which has no validation pass.
+        assert_eq!(
+            OperationListListHash::calculate(&[]).to_base58_check(),
+            "LLoZS2LW3rEi7KYU4ouBQtorua37aWWCtpDmv1n2x3xoKi6sVXLWp"
+        );
+
+        let operations = [
+            vec![OperationHash::from_base58_check(
+                "oom9d3PpjjaMzgg9mZ1pDrF8kjdyzDb41Bd2XE6Y3kRtFHXLku3",
+            )
+            .unwrap()],
+            vec![],
+            vec![
+                OperationHash::from_base58_check(
+                    "oojiRXrrXHgukj8Q7d2AV8QCmJHTM4qpxhZqqbAnuG1RHtnbvim",
+                )
+                .unwrap(),
+                OperationHash::from_base58_check(
+                    "oo3h4gpQBjXaL63GDSiK54mP7sLydhgDQwBfjBWDhGt1gAcnGfA",
+                )
+                .unwrap(),
+            ],
+            vec![],
+        ];
+        let list: Vec<_> = operations
+            .iter()
+            .map(|operations| OperationListHash::calculate(operations))
+            .collect();
+        // Computed with Python's `hashlib.blake2b`, independently of this crate.
+        assert_eq!(
+            list.iter()
+                .map(OperationListHash::to_base58_check)
+                .collect::<Vec<_>>(),
+            [
+                "LowQx8Hd1EHp4uUXETuYWh2cq8WWkdRpxpVJknrJGyc37TwXVQdF",
+                "Lovtotjuae3fq7XNBpVFpSpjWaY8HXReca9gfsUwCKMc7Dy4gPWG",
+                "LowBpg9vgXZVABfg7tVTi8MutGLpeiWrSn8V9uKWYUWAfp9SE3y5",
+                "Lovtotjuae3fq7XNBpVFpSpjWaY8HXReca9gfsUwCKMc7Dy4gPWG",
+            ]
+        );
+        let expected = "LLoaheeK44iyioXMjiS9a6tyT6oK97qRkChEbGJpVb9bt3jmpiCdz";
+
+        assert_eq!(
+            OperationListListHash::calculate(&list).to_base58_check(),
+            expected
+        );
+        assert_eq!(
+            OperationListListHash::calculate_from_operations(&operations).to_base58_check(),
+            expected
+        );
+    }
+
+    #[test]
+    fn block_hash() {
+        // Computed with Python's `hashlib.blake2b`, independently of this crate.
+        let header = hex::decode("00000001").unwrap();
+        assert_eq!(
+            BlockHash::calculate(&header).to_base58_check(),
+            "BL1Ujnq3iQm8D6PzkS9hjdo4n6RHdKRra6ew3QyMJomz44LQ1TD"
+        );
+    }
 }
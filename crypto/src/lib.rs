@@ -4,17 +4,30 @@
 // SPDX-License-Identifier: MIT
 #![forbid(unsafe_code)]
 #![cfg_attr(feature = "fuzzing", feature(no_coverage))]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::string::String;
 use thiserror::Error;
 
 #[macro_use]
 pub mod blake2b;
+pub mod address;
 pub mod base58;
-#[cfg(feature = "bls")]
+pub mod batch;
+#[cfg(any(feature = "bls", feature = "bls-rust"))]
 pub mod bls;
+pub mod encrypted_secret_key;
 #[macro_use]
 pub mod hash;
+pub mod merkle;
+pub mod mnemonic;
+pub mod public_key;
+pub mod public_key_hash;
+pub mod secret_key;
 pub mod signature;
+pub mod slip10;
 
 #[derive(Debug, Error)]
 pub enum CryptoError {
@@ -38,6 +51,17 @@ pub enum CryptoError {
     AlgorithmError(String),
     #[error("Ed25519 error: {0}")]
     Ed25519(ed25519_dalek::SignatureError),
+    #[error("Mnemonic error: {0}")]
+    Mnemonic(bip39::Error),
+}
+
+/// Elliptic curve of a key, signature or public key hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Curve {
+    Ed25519,
+    Secp256k1,
+    P256,
+    Bls,
 }
 
 /// Public key that support hashing.
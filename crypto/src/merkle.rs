@@ -0,0 +1,274 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Merkle trees, as used by Tezos for [OperationListHash], with inclusion proofs.
+//!
+//! The tree is the one described by [merkle_tree]: leaves are the Blake2b
+//! digests of the elements, nodes the Blake2b digest of the concatenation of
+//! their children, and the list is padded with its last element up to a power
+//! of 2.
+//!
+//! [OperationListHash]: crate::hash::OperationListHash
+//! [merkle_tree]: crate::blake2b::merkle_tree
+
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::blake2b::Blake2bHasher;
+
+/// Size of the digests of the tree.
+pub const DIGEST_SIZE: usize = 32;
+
+/// Digest of a leaf or a node of the tree.
+pub type Digest = [u8; DIGEST_SIZE];
+
+fn hash_leaf(leaf: &[u8]) -> Digest {
+    let mut hasher = Blake2bHasher::new();
+    hasher.update(leaf);
+    hasher.finalize()
+}
+
+fn hash_node(left: &Digest, right: &Digest) -> Digest {
+    let mut hasher = Blake2bHasher::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize()
+}
+
+/// Merkle tree of a list of elements.
+///
+/// Only the nodes covering at least one element of the list are stored: the
+/// subtrees made only of padding are all identical at a given level, and are
+/// computed once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+    // levels[0] are the leaves, the last level is the root.
+    levels: Vec<Vec<Digest>>,
+    // padding[k] is the subtree of height k made only of the last element.
+    padding: Vec<Digest>,
+}
+
+impl MerkleTree {
+    /// Computes the tree of the given list.
+    pub fn new<Leaf>(list: &[Leaf]) -> Self
+    where
+        Leaf: AsRef<[u8]>,
+    {
+        let mut levels = Vec::new();
+        let mut padding = Vec::new();
+
+        let mut level: Vec<Digest> = list.iter().map(|leaf| hash_leaf(leaf.as_ref())).collect();
+        if let Some(last) = level.last() {
+            padding.push(*last);
+        }
+
+        while level.len() > 1 {
+            let pad = padding[padding.len() - 1];
+            let next = level
+                .chunks(2)
+                .map(|pair| hash_node(&pair[0], pair.get(1).unwrap_or(&pad)))
+                .collect();
+            padding.push(hash_node(&pad, &pad));
+            levels.push(level);
+            level = next;
+        }
+        levels.push(level);
+
+        MerkleTree { levels, padding }
+    }
+
+    /// Number of elements the tree was computed from.
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Whether the tree was computed from an empty list.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Root of the tree, which is the digest of the empty input for an empty list.
+    pub fn root(&self) -> Digest {
+        match self.levels[self.levels.len() - 1].first() {
+            Some(root) => *root,
+            None => hash_leaf(&[]),
+        }
+    }
+
+    /// Proof that the element at `index` is included in the tree, or `None` if
+    /// `index` is out of bounds.
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let height = self.levels.len() - 1;
+        let path = self.levels[..height]
+            .iter()
+            .enumerate()
+            .map(|(k, level)| {
+                let sibling = (index >> k) ^ 1;
+                level.get(sibling).copied().unwrap_or(self.padding[k])
+            })
+            .collect();
+
+        Some(MerkleProof { index, path })
+    }
+}
+
+/// Proof that an element is included in a [MerkleTree]: the siblings of the
+/// nodes on the path from the element to the root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    index: usize,
+    path: Vec<Digest>,
+}
+
+impl MerkleProof {
+    /// Index of the element in the list.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Siblings of the path from the element to the root, starting from the leaves.
+    pub fn path(&self) -> &[Digest] {
+        &self.path
+    }
+
+    /// Computes the root of the tree from the element the proof is for.
+    pub fn compute_root(&self, leaf: &[u8]) -> Digest {
+        self.path
+            .iter()
+            .enumerate()
+            .fold(hash_leaf(leaf), |node, (k, sibling)| {
+                if (self.index >> k) & 1 == 0 {
+                    hash_node(&node, sibling)
+                } else {
+                    hash_node(sibling, &node)
+                }
+            })
+    }
+
+    /// Verifies that `leaf` is the element at [MerkleProof::index] of the tree of root `root`.
+    ///
+    /// The proof does not commit to the length of the list: as the padding
+    /// repeats the last element, an index past the end of the list proves the
+    /// last element. Callers knowing the length should check the index against it.
+    pub fn verify(&self, leaf: &[u8], root: &[u8]) -> bool {
+        // The index must fit in a tree of that height.
+        let in_tree = u32::try_from(self.path.len())
+            .ok()
+            .and_then(|height| self.index.checked_shr(height))
+            .map_or(true, |rest| rest == 0);
+        in_tree && self.compute_root(leaf) == root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blake2b::{digest_256, digest_all, merkle_tree};
+    use alloc::vec;
+
+    fn leaves(n: u8) -> Vec<Vec<u8>> {
+        (0..n).map(|i| vec![i; 3]).collect()
+    }
+
+    // The recursive implementation `blake2b::merkle_tree` had before delegating
+    // to `MerkleTree`, kept as a reference: a tree of height `degree` over
+    // `list`, where indexes past the end repeat the last element.
+    fn reference_merkle_tree(list: &[Vec<u8>]) -> Vec<u8> {
+        fn inner(list: &[Vec<u8>], degree: u32) -> Vec<u8> {
+            match degree {
+                0 => digest_256(&list[0]),
+                d => {
+                    let middle = 1 << (d - 1);
+                    let right = if middle < list.len() {
+                        &list[middle..]
+                    } else {
+                        &list[list.len() - 1..]
+                    };
+                    digest_all(
+                        [
+                            inner(&list[..middle.min(list.len())], d - 1),
+                            inner(right, d - 1),
+                        ],
+                        32,
+                    )
+                    .unwrap()
+                }
+            }
+        }
+
+        if list.is_empty() {
+            digest_256(&[])
+        } else {
+            inner(list, 64 - (list.len() - 1).leading_zeros())
+        }
+    }
+
+    #[test]
+    fn root_is_reference_merkle_tree() {
+        for n in 0..=17 {
+            let list = leaves(n);
+            let expected = reference_merkle_tree(&list);
+            assert_eq!(
+                MerkleTree::new(&list).root().to_vec(),
+                expected,
+                "{} leaves",
+                n
+            );
+            assert_eq!(merkle_tree(&list), expected, "{} leaves", n);
+        }
+    }
+
+    #[test]
+    fn proofs_verify() {
+        for n in 1..=17 {
+            let list = leaves(n);
+            let tree = MerkleTree::new(&list);
+            let root = tree.root();
+
+            for (index, leaf) in list.iter().enumerate() {
+                let proof = tree.proof(index).unwrap();
+                assert_eq!(proof.index(), index);
+                assert!(proof.verify(leaf, &root), "leaf {} of {}", index, n);
+                assert!(!proof.verify(b"other", &root));
+            }
+            assert_eq!(tree.proof(list.len()), None);
+        }
+    }
+
+    #[test]
+    fn proofs_are_bound_to_index() {
+        let list = leaves(5);
+        let tree = MerkleTree::new(&list);
+        let root = tree.root();
+
+        let proof = tree.proof(1).unwrap();
+        assert!(!proof.verify(&list[0], &root));
+
+        // Padding copies of the last element are at indexes 5 to 7.
+        let proof = tree.proof(4).unwrap();
+        let padded = MerkleProof {
+            index: 7,
+            path: proof.path().to_vec(),
+        };
+        assert!(padded.verify(&list[4], &root));
+
+        let out_of_tree = MerkleProof {
+            index: 4 + 8,
+            path: proof.path().to_vec(),
+        };
+        assert!(!out_of_tree.verify(&list[4], &root));
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree = MerkleTree::new::<Vec<u8>>(&[]);
+        assert!(tree.is_empty());
+        assert_eq!(tree.root().to_vec(), reference_merkle_tree(&[]));
+        assert_eq!(tree.proof(0), None);
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Derivation of ed25519 seeds from BIP39 mnemonics, as done by octez-client
+//! when importing keys from a mnemonic or from a fundraiser account.
+//!
+//! The seed is the first 32 bytes of the BIP39 seed of the mnemonic.
+
+use crate::hash::{HashTrait, SeedEd25519};
+use crate::CryptoError;
+use alloc::format;
+use alloc::string::ToString;
+use bip39::{Language, Mnemonic};
+use zeroize::Zeroize;
+
+/// Number of words of a fundraiser mnemonic.
+pub const FUNDRAISER_WORD_COUNT: usize = 15;
+
+fn parse(mnemonic: &str) -> Result<Mnemonic, CryptoError> {
+    Mnemonic::parse_in(Language::English, mnemonic).map_err(CryptoError::Mnemonic)
+}
+
+fn seed_from(mnemonic: &Mnemonic, passphrase: &str) -> Result<SeedEd25519, CryptoError> {
+    let mut seed = mnemonic.to_seed(passphrase);
+    let result = SeedEd25519::try_from_bytes(&seed[..SeedEd25519::hash_size()]).map_err(|e| {
+        CryptoError::InvalidKey {
+            reason: e.to_string(),
+        }
+    });
+    seed.zeroize();
+    result
+}
+
+/// Derive an ed25519 seed from a BIP39 English `mnemonic`, and an optional
+/// (possibly empty) `passphrase`.
+///
+/// Fails if a word is not part of the wordlist, or if the checksum is invalid.
+pub fn seed_ed25519(mnemonic: &str, passphrase: &str) -> Result<SeedEd25519, CryptoError> {
+    seed_from(&parse(mnemonic)?, passphrase)
+}
+
+/// Derive the ed25519 seed of a fundraiser account, from its 15 words
+/// `mnemonic`, `email` and `password`.
+pub fn fundraiser_seed_ed25519(
+    mnemonic: &str,
+    email: &str,
+    password: &str,
+) -> Result<SeedEd25519, CryptoError> {
+    let mnemonic = parse(mnemonic)?;
+    if mnemonic.word_count() != FUNDRAISER_WORD_COUNT {
+        return Err(CryptoError::Mnemonic(bip39::Error::BadWordCount(
+            mnemonic.word_count(),
+        )));
+    }
+
+    let mut passphrase = format!("{}{}", email, password);
+    let seed = seed_from(&mnemonic, &passphrase);
+    passphrase.zeroize();
+    seed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PublicKeyWithHash;
+
+    #[test]
+    fn test_seed_ed25519() {
+        // BIP39 test vector, from https://github.com/trezor/python-mnemonic
+        let seed = seed_ed25519(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "TREZOR",
+        )
+        .unwrap();
+        assert_eq!(
+            seed.to_base58_check(),
+            "edsk4AobYA4B3RDD6fdr9GChkU9dBsMAm46nWZawx5hHV4PUkKhQJK"
+        );
+    }
+
+    #[test]
+    fn test_fundraiser_seed_ed25519() {
+        let seed = fundraiser_seed_ed25519(
+            "cart will page bench notice leisure penalty medal define odor ride devote cannon setup rescue",
+            "jxmjvauo.guddusns@tezos.example.org",
+            "Fa26j580dQ",
+        )
+        .unwrap();
+        assert_eq!(
+            seed.to_base58_check(),
+            "edsk3E519BZq5bLSQGTFU15tvn3KdLVbiYsgDpvkBsGt29v3wBWDeX"
+        );
+
+        let (pk, _) = seed.keypair().unwrap();
+        assert_eq!(
+            pk.pk_hash().to_base58_check(),
+            "tz1YBMFg1nLAPxBE6djnCPbMRH5PLXQWt8Mg"
+        );
+    }
+
+    #[test]
+    fn test_invalid_mnemonic() {
+        assert!(matches!(
+            seed_ed25519(
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon",
+                ""
+            ),
+            Err(CryptoError::Mnemonic(bip39::Error::InvalidChecksum))
+        ));
+        assert!(matches!(
+            seed_ed25519(
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon tezos",
+                ""
+            ),
+            Err(CryptoError::Mnemonic(bip39::Error::UnknownWord(11)))
+        ));
+        assert!(matches!(
+            fundraiser_seed_ed25519(
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "",
+                ""
+            ),
+            Err(CryptoError::Mnemonic(bip39::Error::BadWordCount(12)))
+        ));
+    }
+}
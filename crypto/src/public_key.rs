@@ -0,0 +1,308 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// Ported from octez: lib_crypto/signature_v1.ml
+//
+// Copyright (c) 2018 Dynamic Ledger Solutions, Inc. <contact@tezos.com>
+// Copyright (c) 2020 Metastate AG <hello@metastate.dev>
+// Copyright (c) 2022 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+use crate::base58::FromBase58CheckError;
+use crate::hash::{
+    Ed25519Signature, HashTrait, HashType, P256Signature, PublicKeyBls, PublicKeyEd25519,
+    PublicKeyP256, PublicKeySecp256k1, Secp256k1Signature,
+};
+use crate::public_key_hash::PublicKeyHash;
+use crate::signature::Signature;
+use crate::{CryptoError, PublicKeySignatureVerifier, PublicKeyWithHash};
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+
+/// Public key of any of the supported curves.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PublicKey {
+    /// Edpk - public key for Ed25519.
+    Ed25519(PublicKeyEd25519),
+    /// Sppk - public key for Secp256k1.
+    Secp256k1(PublicKeySecp256k1),
+    /// P2pk - public key for P256.
+    P256(PublicKeyP256),
+    /// BLpk - public key for BLS.
+    Bls(PublicKeyBls),
+}
+
+impl PublicKey {
+    pub fn from_base58_check(data: &str) -> Result<Self, FromBase58CheckError> {
+        if data.starts_with("edpk") {
+            Ok(PublicKey::Ed25519(PublicKeyEd25519::from_b58check(data)?))
+        } else if data.starts_with("sppk") {
+            Ok(PublicKey::Secp256k1(PublicKeySecp256k1::from_b58check(
+                data,
+            )?))
+        } else if data.starts_with("p2pk") {
+            Ok(PublicKey::P256(PublicKeyP256::from_b58check(data)?))
+        } else if data.starts_with("BLpk") {
+            Ok(PublicKey::Bls(PublicKeyBls::from_b58check(data)?))
+        } else {
+            Err(FromBase58CheckError::IncorrectBase58Prefix)
+        }
+    }
+
+    pub fn to_base58_check(&self) -> String {
+        match self {
+            Self::Ed25519(pk) => pk.to_b58check(),
+            Self::Secp256k1(pk) => pk.to_b58check(),
+            Self::P256(pk) => pk.to_b58check(),
+            Self::Bls(pk) => pk.to_b58check(),
+        }
+    }
+
+    pub fn hash_type(&self) -> HashType {
+        match self {
+            Self::Ed25519(_) => HashType::PublicKeyEd25519,
+            Self::Secp256k1(_) => HashType::PublicKeySecp256k1,
+            Self::P256(_) => HashType::PublicKeyP256,
+            Self::Bls(_) => HashType::PublicKeyBls,
+        }
+    }
+}
+
+impl AsRef<[u8]> for PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::Ed25519(pk) => pk.as_ref(),
+            Self::Secp256k1(pk) => pk.as_ref(),
+            Self::P256(pk) => pk.as_ref(),
+            Self::Bls(pk) => pk.as_ref(),
+        }
+    }
+}
+
+impl TryFrom<&str> for PublicKey {
+    type Error = FromBase58CheckError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        PublicKey::from_base58_check(s)
+    }
+}
+
+impl ::core::str::FromStr for PublicKey {
+    type Err = FromBase58CheckError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PublicKey::from_base58_check(s)
+    }
+}
+
+impl ::core::fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.to_base58_check())
+    }
+}
+
+macro_rules! from_pk_for_public_key {
+    ($pk:ident, $name:ident) => {
+        impl From<$pk> for PublicKey {
+            fn from(pk: $pk) -> Self {
+                Self::$name(pk)
+            }
+        }
+    };
+}
+from_pk_for_public_key!(PublicKeyEd25519, Ed25519);
+from_pk_for_public_key!(PublicKeySecp256k1, Secp256k1);
+from_pk_for_public_key!(PublicKeyP256, P256);
+from_pk_for_public_key!(PublicKeyBls, Bls);
+
+impl PublicKeyWithHash for PublicKey {
+    type Hash = PublicKeyHash;
+
+    fn pk_hash(&self) -> Self::Hash {
+        match self {
+            Self::Ed25519(pk) => pk.pk_hash().into(),
+            Self::Secp256k1(pk) => pk.pk_hash().into(),
+            Self::P256(pk) => pk.pk_hash().into(),
+            Self::Bls(pk) => pk.pk_hash().into(),
+        }
+    }
+}
+
+impl PublicKeySignatureVerifier for PublicKey {
+    type Signature = Signature;
+    type Error = CryptoError;
+
+    /// Verifies the correctness of `bytes` signed as the `signature`, using the
+    /// curve of this public key.
+    ///
+    /// As in octez, ed25519, secp256k1 and p256 signatures are over the blake2b
+    /// digest of `bytes`, while BLS signatures are over `bytes` themselves.
+    /// A generic (`sig`) signature is accepted for all but BLS keys.
+    fn verify_signature(
+        &self,
+        signature: &Self::Signature,
+        bytes: &[u8],
+    ) -> Result<bool, Self::Error> {
+        match (self, signature) {
+            (Self::Ed25519(pk), Signature::Ed25519(sig)) => pk.verify_signature(sig, bytes),
+            (Self::Ed25519(pk), Signature::Unknown(sig)) => {
+                pk.verify_signature(&Ed25519Signature::from(sig.clone()), bytes)
+            }
+            (Self::Secp256k1(pk), Signature::Secp256k1(sig)) => {
+                pk.verify_signature(sig, &crate::blake2b::digest_256(bytes))
+            }
+            (Self::Secp256k1(pk), Signature::Unknown(sig)) => pk.verify_signature(
+                &Secp256k1Signature::from(sig.clone()),
+                &crate::blake2b::digest_256(bytes),
+            ),
+            (Self::P256(pk), Signature::P256(sig)) => {
+                pk.verify_signature(sig, &crate::blake2b::digest_256(bytes))
+            }
+            (Self::P256(pk), Signature::Unknown(sig)) => pk.verify_signature(
+                &P256Signature::from(sig.clone()),
+                &crate::blake2b::digest_256(bytes),
+            ),
+            #[cfg(any(feature = "bls", feature = "bls-rust"))]
+            (Self::Bls(pk), Signature::Bls(sig)) => pk.verify_signature(sig, bytes),
+            #[cfg(not(any(feature = "bls", feature = "bls-rust")))]
+            (Self::Bls(_), Signature::Bls(_)) => Err(CryptoError::Unsupported("bls")),
+            _ => Err(CryptoError::InvalidSignature),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{SecretKeyEd25519, SecretKeyP256, SecretKeySecp256k1, SeedEd25519};
+
+    #[test]
+    fn test_b58_round_trip() {
+        let test = |pk: &str, hash_type| {
+            let public_key = PublicKey::from_base58_check(pk).expect("valid public key");
+            assert_eq!(public_key.hash_type(), hash_type);
+            assert_eq!(public_key.to_base58_check(), pk);
+            assert_eq!(public_key.to_string(), pk);
+            assert_eq!(pk.parse::<PublicKey>().unwrap(), public_key);
+        };
+
+        test(
+            "edpkuBknW28nW72KG6RoHtYW7p12T6GKc7nAbwYX5m8Wd9sDVC9yav",
+            HashType::PublicKeyEd25519,
+        );
+        test(
+            "sppk7bgK42i4MqXdSToddBHCmVgUvQqgkLvpgpocGnGPtGYhi4FS2DQ",
+            HashType::PublicKeySecp256k1,
+        );
+        test(
+            "p2pk653ADWvBsiKHXJEGx5VbsQzVHvjTfwfJoy5fKCALiQQK9q91oLk",
+            HashType::PublicKeyP256,
+        );
+        test(
+            "BLpk1mJXuRWVxRJRkUES7E16u4KeKMGibFtR995FxSFzeyMm8ckngdo4Cx4P3KWeRQ1NeY3iEWwq",
+            HashType::PublicKeyBls,
+        );
+    }
+
+    #[test]
+    fn test_b58_invalid_prefix() {
+        assert!(matches!(
+            PublicKey::from_base58_check("tz1KqTpEZ7Yob7QbPE4Hy4Wo8fHG8LhKxZSx"),
+            Err(FromBase58CheckError::IncorrectBase58Prefix)
+        ));
+    }
+
+    #[test]
+    fn test_pk_hash() {
+        let test = |pk: &str, pkh: &str| {
+            let public_key = PublicKey::from_base58_check(pk).unwrap();
+            assert_eq!(public_key.pk_hash().to_string(), pkh);
+        };
+
+        test(
+            "edpkuBknW28nW72KG6RoHtYW7p12T6GKc7nAbwYX5m8Wd9sDVC9yav",
+            "tz1KqTpEZ7Yob7QbPE4Hy4Wo8fHG8LhKxZSx",
+        );
+        test(
+            "sppk7bgK42i4MqXdSToddBHCmVgUvQqgkLvpgpocGnGPtGYhi4FS2DQ",
+            "tz2TRpDZTo2uWH1BuiQD58pmywTmD1kt6NWt",
+        );
+        test(
+            "p2pk653ADWvBsiKHXJEGx5VbsQzVHvjTfwfJoy5fKCALiQQK9q91oLk",
+            "tz3V4z2s9854NWrWqX7ndmAUB558AdDB8XMw",
+        );
+        test(
+            "BLpk1mJXuRWVxRJRkUES7E16u4KeKMGibFtR995FxSFzeyMm8ckngdo4Cx4P3KWeRQ1NeY3iEWwq",
+            "tz4NPp9xHJMgoRQwE2iL66NnfeBcnEoskeuj",
+        );
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let msg = b"hello, world";
+
+        let seed = SecretKeyEd25519::from_b58check(
+            "edsk3gUfUPyBSfrS9CCgmCiQsTCHGkviBDusMxDJstFtojtc1zcpsh",
+        )
+        .unwrap();
+        let (pk, sk) = SeedEd25519::try_from_bytes(seed.as_ref())
+            .unwrap()
+            .keypair()
+            .unwrap();
+        let ed25519 = (PublicKey::from(pk), Signature::from(sk.sign(msg).unwrap()));
+
+        let sk = SecretKeySecp256k1::from_b58check(
+            "spsk2SV3aKzS1Q1edSkxLMzTiewSyij7grAvGnsEQMYA1k3xvBT8DF",
+        )
+        .unwrap();
+        let secp256k1 = (
+            PublicKey::from(sk.derive_pk().unwrap()),
+            Signature::from(sk.sign(msg).unwrap()),
+        );
+
+        let sk =
+            SecretKeyP256::from_b58check("p2sk3aH3xfzujyDbCpDuGLSsPt83s6cmn3qVh9HwA3rmDG14HXUpUv")
+                .unwrap();
+        let p256 = (
+            PublicKey::from(sk.derive_pk().unwrap()),
+            Signature::from(sk.sign(msg).unwrap()),
+        );
+
+        for (pk, sig) in [&ed25519, &secp256k1, &p256] {
+            assert!(matches!(pk.verify_signature(sig, msg), Ok(true)));
+
+            let generic = Signature::Unknown(sig.clone().try_into().unwrap());
+            assert!(matches!(pk.verify_signature(&generic, msg), Ok(true)));
+
+            assert!(!matches!(pk.verify_signature(sig, b"other"), Ok(true)));
+        }
+
+        assert!(matches!(
+            ed25519.0.verify_signature(&secp256k1.1, msg),
+            Err(CryptoError::InvalidSignature)
+        ));
+        assert!(matches!(
+            p256.0.verify_signature(&ed25519.1, msg),
+            Err(CryptoError::InvalidSignature)
+        ));
+    }
+
+    #[cfg(any(feature = "bls", feature = "bls-rust"))]
+    #[test]
+    fn test_verify_signature_bls() {
+        let msg = b"hello, world";
+        let (sk, pk) = crate::bls::keypair_from_ikm([7; 32]).unwrap();
+        let pk = PublicKey::from(pk);
+        let sig = Signature::from(sk.sign(msg).unwrap());
+
+        assert!(matches!(pk.verify_signature(&sig, msg), Ok(true)));
+        assert!(!matches!(pk.verify_signature(&sig, b"other"), Ok(true)));
+
+        let generic = Signature::Unknown([0; 64].to_vec().try_into().unwrap());
+        assert!(matches!(
+            pk.verify_signature(&generic, msg),
+            Err(CryptoError::InvalidSignature)
+        ));
+    }
+}
@@ -0,0 +1,172 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// Ported from octez: lib_crypto/signature_v1.ml
+//
+// Copyright (c) 2018 Dynamic Ledger Solutions, Inc. <contact@tezos.com>
+// Copyright (c) 2020 Metastate AG <hello@metastate.dev>
+// Copyright (c) 2022 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+use crate::base58::FromBase58CheckError;
+use crate::hash::{
+    ContractTz1Hash, ContractTz2Hash, ContractTz3Hash, ContractTz4Hash, HashTrait, HashType,
+};
+use crate::Curve;
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+
+/// Hash of a public key, of any of the supported curves - also known as
+/// the address of an implicit account.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PublicKeyHash {
+    /// Tz1 - public key hash for Ed25519 keys.
+    Ed25519(ContractTz1Hash),
+    /// Tz2 - public key hash for Secp256k1 keys.
+    Secp256k1(ContractTz2Hash),
+    /// Tz3 - public key hash for P256 keys.
+    P256(ContractTz3Hash),
+    /// Tz4 - public key hash for BLS keys.
+    Bls(ContractTz4Hash),
+}
+
+impl PublicKeyHash {
+    pub fn from_base58_check(data: &str) -> Result<Self, FromBase58CheckError> {
+        if data.starts_with("tz1") {
+            Ok(PublicKeyHash::Ed25519(ContractTz1Hash::from_b58check(
+                data,
+            )?))
+        } else if data.starts_with("tz2") {
+            Ok(PublicKeyHash::Secp256k1(ContractTz2Hash::from_b58check(
+                data,
+            )?))
+        } else if data.starts_with("tz3") {
+            Ok(PublicKeyHash::P256(ContractTz3Hash::from_b58check(data)?))
+        } else if data.starts_with("tz4") {
+            Ok(PublicKeyHash::Bls(ContractTz4Hash::from_b58check(data)?))
+        } else {
+            Err(FromBase58CheckError::IncorrectBase58Prefix)
+        }
+    }
+
+    pub fn to_base58_check(&self) -> String {
+        match self {
+            Self::Ed25519(h) => h.to_b58check(),
+            Self::Secp256k1(h) => h.to_b58check(),
+            Self::P256(h) => h.to_b58check(),
+            Self::Bls(h) => h.to_b58check(),
+        }
+    }
+
+    pub fn hash_type(&self) -> HashType {
+        match self {
+            Self::Ed25519(_) => HashType::ContractTz1Hash,
+            Self::Secp256k1(_) => HashType::ContractTz2Hash,
+            Self::P256(_) => HashType::ContractTz3Hash,
+            Self::Bls(_) => HashType::ContractTz4Hash,
+        }
+    }
+
+    /// Curve of the public key this is the hash of.
+    pub fn curve(&self) -> Curve {
+        match self {
+            Self::Ed25519(_) => Curve::Ed25519,
+            Self::Secp256k1(_) => Curve::Secp256k1,
+            Self::P256(_) => Curve::P256,
+            Self::Bls(_) => Curve::Bls,
+        }
+    }
+}
+
+impl AsRef<[u8]> for PublicKeyHash {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::Ed25519(h) => h.as_ref(),
+            Self::Secp256k1(h) => h.as_ref(),
+            Self::P256(h) => h.as_ref(),
+            Self::Bls(h) => h.as_ref(),
+        }
+    }
+}
+
+impl TryFrom<&str> for PublicKeyHash {
+    type Error = FromBase58CheckError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        PublicKeyHash::from_base58_check(s)
+    }
+}
+
+impl ::core::str::FromStr for PublicKeyHash {
+    type Err = FromBase58CheckError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PublicKeyHash::from_base58_check(s)
+    }
+}
+
+impl ::core::fmt::Display for PublicKeyHash {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.to_base58_check())
+    }
+}
+
+macro_rules! from_h_for_pkh {
+    ($h:ident, $name:ident) => {
+        impl From<$h> for PublicKeyHash {
+            fn from(h: $h) -> Self {
+                Self::$name(h)
+            }
+        }
+    };
+}
+from_h_for_pkh!(ContractTz1Hash, Ed25519);
+from_h_for_pkh!(ContractTz2Hash, Secp256k1);
+from_h_for_pkh!(ContractTz3Hash, P256);
+from_h_for_pkh!(ContractTz4Hash, Bls);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_b58_round_trip() {
+        let test = |pkh: &str, curve| {
+            let hash = PublicKeyHash::from_base58_check(pkh).expect("valid public key hash");
+            assert_eq!(hash.curve(), curve);
+            assert_eq!(hash.to_base58_check(), pkh);
+            assert_eq!(hash.to_string(), pkh);
+            assert_eq!(pkh.parse::<PublicKeyHash>().unwrap(), hash);
+        };
+
+        test("tz1KqTpEZ7Yob7QbPE4Hy4Wo8fHG8LhKxZSx", Curve::Ed25519);
+        test("tz2TRpDZTo2uWH1BuiQD58pmywTmD1kt6NWt", Curve::Secp256k1);
+        test("tz3V4z2s9854NWrWqX7ndmAUB558AdDB8XMw", Curve::P256);
+        test("tz4NPp9xHJMgoRQwE2iL66NnfeBcnEoskeuj", Curve::Bls);
+    }
+
+    #[test]
+    fn test_b58_invalid() {
+        assert!(matches!(
+            PublicKeyHash::from_base58_check("KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi"),
+            Err(FromBase58CheckError::IncorrectBase58Prefix)
+        ));
+        assert!(matches!(
+            PublicKeyHash::from_base58_check("tz1KqTpEZ7Yob7QbPE4Hy4Wo8fHG8LhKxZSy"),
+            Err(FromBase58CheckError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn test_json() {
+        let pkh = "tz2TRpDZTo2uWH1BuiQD58pmywTmD1kt6NWt";
+        let hash = PublicKeyHash::from_base58_check(pkh).unwrap();
+
+        let json = serde_json::to_string(&hash).unwrap();
+        assert_eq!(json, format!("\"{}\"", pkh));
+
+        let decoded: PublicKeyHash = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, hash);
+    }
+}
@@ -0,0 +1,266 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// Ported from octez: lib_crypto/signature_v1.ml
+//
+// Copyright (c) 2018 Dynamic Ledger Solutions, Inc. <contact@tezos.com>
+// Copyright (c) 2020 Metastate AG <hello@metastate.dev>
+// Copyright (c) 2022 Nomadic Labs <contact@nomadic-labs.com>
+//
+// SPDX-License-Identifier: MIT
+
+use crate::base58::FromBase58CheckError;
+use crate::hash::{
+    HashTrait, LegacySecretKeyEd25519, SecretKeyBls, SecretKeyEd25519, SecretKeyP256,
+    SecretKeySecp256k1,
+};
+use crate::public_key::PublicKey;
+use crate::signature::Signature;
+use crate::{CryptoError, Curve};
+use alloc::string::String;
+use zeroize::Zeroize;
+
+/// Secret key of any of the supported curves.
+///
+/// The secret material is zeroized when the key is dropped, as are the copies
+/// made by each curve's library to derive public keys and sign.
+#[derive(Clone, PartialEq, Eq)]
+pub enum SecretKey {
+    /// Edsk - secret key (seed) for Ed25519.
+    Ed25519(SecretKeyEd25519),
+    /// Spsk - secret key for Secp256k1.
+    Secp256k1(SecretKeySecp256k1),
+    /// P2sk - secret key for P256.
+    P256(SecretKeyP256),
+    /// BLsk - secret key for BLS.
+    Bls(SecretKeyBls),
+}
+
+impl SecretKey {
+    pub fn from_base58_check(data: &str) -> Result<Self, FromBase58CheckError> {
+        if data.starts_with("edsk") {
+            let sk = SecretKeyEd25519::from_b58check(data).or_else(|error| {
+                // Older wallets export the 64-byte form: the seed followed by its public key.
+                let legacy = LegacySecretKeyEd25519::from_b58check(data).map_err(|_| error)?;
+                // The embedded public key acts as a checksum of the seed.
+                SecretKeyEd25519::try_from(&legacy)
+                    .map_err(|_| FromBase58CheckError::InvalidChecksum)
+            })?;
+            Ok(SecretKey::Ed25519(sk))
+        } else if data.starts_with("spsk") {
+            Ok(SecretKey::Secp256k1(SecretKeySecp256k1::from_b58check(
+                data,
+            )?))
+        } else if data.starts_with("p2sk") {
+            Ok(SecretKey::P256(SecretKeyP256::from_b58check(data)?))
+        } else if data.starts_with("BLsk") {
+            Ok(SecretKey::Bls(SecretKeyBls::from_b58check(data)?))
+        } else {
+            Err(FromBase58CheckError::IncorrectBase58Prefix)
+        }
+    }
+
+    pub fn to_base58_check(&self) -> String {
+        match self {
+            Self::Ed25519(sk) => sk.to_b58check(),
+            Self::Secp256k1(sk) => sk.to_b58check(),
+            Self::P256(sk) => sk.to_b58check(),
+            Self::Bls(sk) => sk.to_b58check(),
+        }
+    }
+
+    pub fn curve(&self) -> Curve {
+        match self {
+            Self::Ed25519(_) => Curve::Ed25519,
+            Self::Secp256k1(_) => Curve::Secp256k1,
+            Self::P256(_) => Curve::P256,
+            Self::Bls(_) => Curve::Bls,
+        }
+    }
+
+    /// Derive the public key for the current secret key.
+    pub fn public_key(&self) -> Result<PublicKey, CryptoError> {
+        match self {
+            Self::Ed25519(sk) => sk.derive_pk().map(PublicKey::Ed25519),
+            Self::Secp256k1(sk) => sk.derive_pk().map(PublicKey::Secp256k1),
+            Self::P256(sk) => sk.derive_pk().map(PublicKey::P256),
+            #[cfg(any(feature = "bls", feature = "bls-rust"))]
+            Self::Bls(sk) => sk.derive_pk().map(PublicKey::Bls),
+            #[cfg(not(any(feature = "bls", feature = "bls-rust")))]
+            Self::Bls(_) => Err(CryptoError::Unsupported("bls")),
+        }
+    }
+
+    /// Sign `data`, as done by octez for the curve of this key.
+    ///
+    /// Ed25519, secp256k1 and p256 keys sign the blake2b digest of `data`,
+    /// while BLS keys sign `data` prefixed by their public key.
+    pub fn sign<I>(&self, data: I) -> Result<Signature, CryptoError>
+    where
+        I: AsRef<[u8]>,
+    {
+        match self {
+            Self::Ed25519(sk) => sk.sign(data).map(Signature::Ed25519),
+            Self::Secp256k1(sk) => sk.sign(data).map(Signature::Secp256k1),
+            Self::P256(sk) => sk.sign(data).map(Signature::P256),
+            #[cfg(any(feature = "bls", feature = "bls-rust"))]
+            Self::Bls(sk) => sk.sign(data).map(Signature::Bls),
+            #[cfg(not(any(feature = "bls", feature = "bls-rust")))]
+            Self::Bls(_) => Err(CryptoError::Unsupported("bls")),
+        }
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        match self {
+            Self::Ed25519(sk) => sk.0.zeroize(),
+            Self::Secp256k1(sk) => sk.0.zeroize(),
+            Self::P256(sk) => sk.0.zeroize(),
+            Self::Bls(sk) => sk.0.zeroize(),
+        }
+    }
+}
+
+impl ::core::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        // Never leak the secret material into logs.
+        write!(f, "SecretKey::{:?}(..)", self.curve())
+    }
+}
+
+impl TryFrom<&str> for SecretKey {
+    type Error = FromBase58CheckError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        SecretKey::from_base58_check(s)
+    }
+}
+
+impl ::core::str::FromStr for SecretKey {
+    type Err = FromBase58CheckError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SecretKey::from_base58_check(s)
+    }
+}
+
+macro_rules! from_sk_for_secret_key {
+    ($sk:ident, $name:ident) => {
+        impl From<$sk> for SecretKey {
+            fn from(sk: $sk) -> Self {
+                Self::$name(sk)
+            }
+        }
+    };
+}
+from_sk_for_secret_key!(SecretKeyEd25519, Ed25519);
+from_sk_for_secret_key!(SecretKeySecp256k1, Secp256k1);
+from_sk_for_secret_key!(SecretKeyP256, P256);
+from_sk_for_secret_key!(SecretKeyBls, Bls);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PublicKeySignatureVerifier, PublicKeyWithHash};
+
+    #[test]
+    fn test_public_key() {
+        let test = |sk: &str, pk: &str, pkh: &str| {
+            let secret_key = SecretKey::from_base58_check(sk).expect("valid secret key");
+            assert_eq!(secret_key.to_base58_check(), sk);
+
+            let public_key = secret_key.public_key().unwrap();
+            assert_eq!(public_key.to_base58_check(), pk);
+            assert_eq!(public_key.pk_hash().to_base58_check(), pkh);
+        };
+
+        test(
+            "edsk3gUfUPyBSfrS9CCgmCiQsTCHGkviBDusMxDJstFtojtc1zcpsh",
+            "edpkuBknW28nW72KG6RoHtYW7p12T6GKc7nAbwYX5m8Wd9sDVC9yav",
+            "tz1KqTpEZ7Yob7QbPE4Hy4Wo8fHG8LhKxZSx",
+        );
+        test(
+            "spsk2SV3aKzS1Q1edSkxLMzTiewSyij7grAvGnsEQMYA1k3xvBT8DF",
+            "sppk7bgK42i4MqXdSToddBHCmVgUvQqgkLvpgpocGnGPtGYhi4FS2DQ",
+            "tz2TRpDZTo2uWH1BuiQD58pmywTmD1kt6NWt",
+        );
+        test(
+            "p2sk3aH3xfzujyDbCpDuGLSsPt83s6cmn3qVh9HwA3rmDG14HXUpUv",
+            "p2pk653ADWvBsiKHXJEGx5VbsQzVHvjTfwfJoy5fKCALiQQK9q91oLk",
+            "tz3V4z2s9854NWrWqX7ndmAUB558AdDB8XMw",
+        );
+    }
+
+    #[test]
+    fn test_legacy_ed25519() {
+        let sk = SecretKey::from_base58_check(
+            "edskRuR1azSfboG86YPTyxrQgosh5zChf5bVDmptqLTb5EuXAm9rsnDYfTKhq7rDQujdn5WWzwUMeV3agaZ6J2vPQT58jJAJPi",
+        )
+        .expect("valid legacy secret key");
+        assert_eq!(
+            sk.to_base58_check(),
+            "edsk3gUfUPyBSfrS9CCgmCiQsTCHGkviBDusMxDJstFtojtc1zcpsh"
+        );
+
+        // Same seed, with the public key of another account.
+        assert!(matches!(
+            SecretKey::from_base58_check(
+                "edskRuR1azSfboG86YPTyxrQgosh5zChf5bVDmptqLTb5EuXAkM2hhi4UW9XotHz89GgWDtfvDme95WjHR9HszPAVguv8hhvRR",
+            ),
+            Err(FromBase58CheckError::InvalidChecksum)
+        ));
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let msg = b"hello, world";
+        let mut keys = vec![
+            "edsk3gUfUPyBSfrS9CCgmCiQsTCHGkviBDusMxDJstFtojtc1zcpsh",
+            "spsk2SV3aKzS1Q1edSkxLMzTiewSyij7grAvGnsEQMYA1k3xvBT8DF",
+            "p2sk3aH3xfzujyDbCpDuGLSsPt83s6cmn3qVh9HwA3rmDG14HXUpUv",
+        ];
+        if cfg!(any(feature = "bls", feature = "bls-rust")) {
+            keys.push("BLsk1WTwJFkLU2P57itDq1cgEUqJK7Fwygvtj49vT4HeLfNBXRgpDA");
+        }
+
+        for sk in keys {
+            let sk: SecretKey = sk.parse().unwrap();
+            let pk = sk.public_key().unwrap();
+            let signature = sk.sign(msg).unwrap();
+
+            assert!(matches!(pk.verify_signature(&signature, msg), Ok(true)));
+            assert!(!matches!(
+                pk.verify_signature(&signature, b"other"),
+                Ok(true)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_sign_known() {
+        let sk =
+            SecretKey::from_base58_check("spsk2SV3aKzS1Q1edSkxLMzTiewSyij7grAvGnsEQMYA1k3xvBT8DF")
+                .unwrap();
+        let msg =
+            hex::decode("0300000000000000000000000000000000000000000000000000000000000000006c00")
+                .unwrap();
+
+        assert_eq!(
+            sk.sign(msg).unwrap().to_base58_check(),
+            "spsig1eSuSkcF4eqLFhJgsEkvyjmAAtz68aNtGo89KhbtRPMBNPZAaFbNkbdPoe5mJWkQxq6n1vNL6UvNMFUX7rPa1wrPey7SsZ"
+        );
+    }
+
+    #[test]
+    fn test_invalid_prefix_and_debug() {
+        assert!(matches!(
+            SecretKey::from_base58_check("edpkuBknW28nW72KG6RoHtYW7p12T6GKc7nAbwYX5m8Wd9sDVC9yav"),
+            Err(FromBase58CheckError::IncorrectBase58Prefix)
+        ));
+
+        let sk =
+            SecretKey::from_base58_check("edsk3gUfUPyBSfrS9CCgmCiQsTCHGkviBDusMxDJstFtojtc1zcpsh")
+                .unwrap();
+        assert_eq!(format!("{:?}", sk), "SecretKey::Ed25519(..)");
+    }
+}
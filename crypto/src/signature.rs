@@ -14,6 +14,8 @@ use crate::hash::{
     BlsSignature, Ed25519Signature, FromBytesError, HashTrait, HashType, P256Signature,
     Secp256k1Signature, UnknownSignature,
 };
+use alloc::string::String;
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -129,8 +131,8 @@ impl ::core::str::FromStr for Signature {
     }
 }
 
-impl ::std::fmt::Display for Signature {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl ::core::fmt::Display for Signature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         // TODO - this could be done without the need to perform a heap allocation.
         write!(f, "{}", self.to_base58_check())
     }
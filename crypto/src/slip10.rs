@@ -0,0 +1,554 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Hierarchical deterministic derivation of secret keys, following
+//! [SLIP-0010](https://github.com/satoshilabs/slips/blob/master/slip-0010.md).
+//!
+//! For secp256k1, SLIP-0010 is identical to BIP32. Ed25519 only supports
+//! hardened derivation.
+//!
+//! Tezos wallets (Ledger, Kukai, ...) derive accounts along
+//! `m/44'/1729'/account'/0'`, see [DerivationPath::tezos].
+
+use crate::hash::{HashTrait, SecretKeyEd25519, SecretKeyP256, SecretKeySecp256k1};
+use crate::secret_key::SecretKey;
+use crate::{CryptoError, Curve};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use cryptoxide::hmac::Hmac;
+use cryptoxide::mac::Mac;
+use cryptoxide::sha2::Sha512;
+use thiserror::Error;
+use zeroize::Zeroize;
+
+/// Offset of hardened child indices.
+pub const HARDENED: u32 = 0x8000_0000;
+
+/// BIP44 coin type registered for Tezos.
+const TEZOS_COIN_TYPE: u32 = 1729;
+
+const CHAIN_CODE_SIZE: usize = 32;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DerivationPathError {
+    #[error("derivation path must start with `m`")]
+    MissingMaster,
+    #[error("invalid derivation path index `{0}`")]
+    InvalidIndex(String),
+}
+
+/// Path of child indices, from the master key, such as `m/44'/1729'/0'/0'`.
+///
+/// Hardened indices are marked by `'` or `h`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// The `m/44'/1729'/account'/0'` path used by Tezos wallets.
+    ///
+    /// Fails if `account` is not below [HARDENED], as it would not be the
+    /// index of its hardened child.
+    pub fn tezos(account: u32) -> Result<Self, DerivationPathError> {
+        if account >= HARDENED {
+            return Err(DerivationPathError::InvalidIndex(account.to_string()));
+        }
+        Ok(Self(vec![
+            44 | HARDENED,
+            TEZOS_COIN_TYPE | HARDENED,
+            account | HARDENED,
+            HARDENED,
+        ]))
+    }
+
+    /// Child indices, hardened indices being offset by [HARDENED].
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl From<Vec<u32>> for DerivationPath {
+    fn from(indices: Vec<u32>) -> Self {
+        Self(indices)
+    }
+}
+
+impl ::core::str::FromStr for DerivationPath {
+    type Err = DerivationPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = s.split('/');
+        if segments.next() != Some("m") {
+            return Err(DerivationPathError::MissingMaster);
+        }
+
+        segments
+            .map(|segment| {
+                let (index, offset) = match segment
+                    .strip_suffix('\'')
+                    .or_else(|| segment.strip_suffix('h'))
+                {
+                    Some(index) => (index, HARDENED),
+                    None => (segment, 0),
+                };
+                // Only accept canonical decimal indices, without sign or leading zeros.
+                match index.parse::<u32>() {
+                    Ok(i) if i < HARDENED && i.to_string() == index => Ok(i | offset),
+                    _ => Err(DerivationPathError::InvalidIndex(segment.to_string())),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            if index & HARDENED != 0 {
+                write!(f, "/{}'", index & !HARDENED)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Secret key, together with the chain code needed to derive its children.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExtendedSecretKey {
+    secret_key: SecretKey,
+    chain_code: [u8; CHAIN_CODE_SIZE],
+}
+
+impl ExtendedSecretKey {
+    /// Master key of `curve` for the given `seed` - typically the BIP39 seed
+    /// of a mnemonic.
+    pub fn from_seed(curve: Curve, seed: &[u8]) -> Result<Self, CryptoError> {
+        let hmac_key: &[u8] = match curve {
+            Curve::Ed25519 => b"ed25519 seed",
+            Curve::Secp256k1 => b"Bitcoin seed",
+            Curve::P256 => b"Nist256p1 seed",
+            Curve::Bls => return Err(CryptoError::Unsupported("bls")),
+        };
+
+        let mut i = hmac_sha512(hmac_key, &[seed]);
+        loop {
+            let (il, ir) = i.split_at(32);
+            if let Some(secret_key) = master_secret_key(curve, il) {
+                let result = Self::new(secret_key, ir);
+                i.zeroize();
+                return result;
+            }
+            // The key is invalid for the curve: retry with `I` as the seed.
+            let next = hmac_sha512(hmac_key, &[&i]);
+            i.zeroize();
+            i = next;
+        }
+    }
+
+    fn new(secret_key: SecretKey, chain_code: &[u8]) -> Result<Self, CryptoError> {
+        Ok(Self {
+            secret_key,
+            chain_code: chain_code
+                .try_into()
+                .map_err(|_| CryptoError::InvalidKeySize {
+                    expected: CHAIN_CODE_SIZE,
+                    actual: chain_code.len(),
+                })?,
+        })
+    }
+
+    pub fn secret_key(&self) -> &SecretKey {
+        &self.secret_key
+    }
+
+    pub fn chain_code(&self) -> &[u8; CHAIN_CODE_SIZE] {
+        &self.chain_code
+    }
+
+    /// Derive the child key at `index`, which is hardened when greater than
+    /// or equal to [HARDENED].
+    pub fn derive_child(&self, index: u32) -> Result<Self, CryptoError> {
+        let key = self.key_bytes();
+        let mut data = if index & HARDENED != 0 {
+            [&[0], key, &index.to_be_bytes()].concat()
+        } else {
+            let public_key = match &self.secret_key {
+                SecretKey::Secp256k1(sk) => sk.derive_pk()?.0,
+                SecretKey::P256(sk) => sk.derive_pk()?.0,
+                _ => return Err(CryptoError::Unsupported("non-hardened ed25519 derivation")),
+            };
+            [&public_key, &index.to_be_bytes()[..]].concat()
+        };
+
+        loop {
+            let mut i = hmac_sha512(&self.chain_code, &[&data]);
+            data.zeroize();
+            let (il, ir) = i.split_at(32);
+            if let Some(secret_key) = child_secret_key(&self.secret_key, key, il) {
+                let result = Self::new(secret_key?, ir);
+                i.zeroize();
+                return result;
+            }
+            // The key is invalid for the curve: retry with `0x01 || IR || index`.
+            data = [&[1], ir, &index.to_be_bytes()].concat();
+            i.zeroize();
+        }
+    }
+
+    /// Derive the descendant key at `path`.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, CryptoError> {
+        path.indices()
+            .iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    fn key_bytes(&self) -> &[u8] {
+        match &self.secret_key {
+            SecretKey::Ed25519(sk) => &sk.0,
+            SecretKey::Secp256k1(sk) => &sk.0,
+            SecretKey::P256(sk) => &sk.0,
+            SecretKey::Bls(sk) => &sk.0,
+        }
+    }
+}
+
+impl Drop for ExtendedSecretKey {
+    fn drop(&mut self) {
+        self.chain_code.zeroize();
+    }
+}
+
+impl fmt::Debug for ExtendedSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never leak the secret material into logs.
+        write!(f, "ExtendedSecretKey::{:?}(..)", self.secret_key.curve())
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::new(Sha512::new(), key);
+    for data in data {
+        mac.input(data);
+    }
+    let mut out = [0; 64];
+    mac.raw_result(&mut out);
+    out
+}
+
+/// Returns `None` when `il` is not a valid secret key of the curve.
+fn master_secret_key(curve: Curve, il: &[u8]) -> Option<SecretKey> {
+    match curve {
+        Curve::Ed25519 => SecretKeyEd25519::try_from_bytes(il)
+            .ok()
+            .map(SecretKey::Ed25519),
+        Curve::Secp256k1 => {
+            libsecp256k1::SecretKey::parse_slice(il).ok()?;
+            SecretKeySecp256k1::try_from_bytes(il)
+                .ok()
+                .map(SecretKey::Secp256k1)
+        }
+        Curve::P256 => {
+            p256_scalar(il).filter(|s| !bool::from(s.is_zero()))?;
+            SecretKeyP256::try_from_bytes(il).ok().map(SecretKey::P256)
+        }
+        Curve::Bls => None,
+    }
+}
+
+/// Returns `None` when the child key derived from `il` is invalid, in which
+/// case derivation must be retried.
+fn child_secret_key(
+    parent: &SecretKey,
+    key: &[u8],
+    il: &[u8],
+) -> Option<Result<SecretKey, CryptoError>> {
+    let to_invalid_key = |e: crate::hash::FromBytesError| CryptoError::InvalidKey {
+        reason: e.to_string(),
+    };
+
+    match parent {
+        SecretKey::Ed25519(_) => Some(
+            SecretKeyEd25519::try_from_bytes(il)
+                .map(SecretKey::Ed25519)
+                .map_err(to_invalid_key),
+        ),
+        SecretKey::Secp256k1(_) => {
+            let tweak = libsecp256k1::SecretKey::parse_slice(il).ok()?;
+            let mut child = match libsecp256k1::SecretKey::parse_slice(key) {
+                Ok(key) => key,
+                Err(e) => {
+                    return Some(Err(CryptoError::InvalidKey {
+                        reason: e.to_string(),
+                    }))
+                }
+            };
+            child.tweak_add_assign(&tweak).ok()?;
+            Some(
+                SecretKeySecp256k1::try_from_bytes(&child.serialize())
+                    .map(SecretKey::Secp256k1)
+                    .map_err(to_invalid_key),
+            )
+        }
+        SecretKey::P256(_) => {
+            let tweak = p256_scalar(il)?;
+            let key = match p256_scalar(key) {
+                Some(key) => key,
+                None => {
+                    return Some(Err(CryptoError::InvalidKey {
+                        reason: "invalid p256 scalar".to_string(),
+                    }))
+                }
+            };
+            let child = key + tweak;
+            if bool::from(child.is_zero()) {
+                return None;
+            }
+            Some(
+                SecretKeyP256::try_from_bytes(&child.to_bytes())
+                    .map(SecretKey::P256)
+                    .map_err(to_invalid_key),
+            )
+        }
+        SecretKey::Bls(_) => Some(Err(CryptoError::Unsupported("bls"))),
+    }
+}
+
+/// Returns `None` when `bytes` is not lower than the order of the curve.
+fn p256_scalar(bytes: &[u8]) -> Option<p256::Scalar> {
+    use p256::elliptic_curve::group::ff::PrimeField;
+
+    if bytes.len() != 32 {
+        return None;
+    }
+    p256::Scalar::from_repr(*p256::FieldBytes::from_slice(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    fn check_chain(curve: Curve, path: &str, expected: &[(&str, &str)]) {
+        let seed = hex::decode(SEED).unwrap();
+        let mut key = ExtendedSecretKey::from_seed(curve, &seed).unwrap();
+        let path: DerivationPath = path.parse().unwrap();
+
+        let mut indices = path.indices().iter();
+        for (chain_code, secret_key) in expected {
+            assert_eq!(hex::encode(key.chain_code()), *chain_code);
+            assert_eq!(hex::encode(key.key_bytes()), *secret_key);
+            if let Some(index) = indices.next() {
+                key = key.derive_child(*index).unwrap();
+            }
+        }
+    }
+
+    // Test vector 1 for ed25519, from SLIP-0010.
+    #[test]
+    fn test_slip10_ed25519() {
+        check_chain(
+            Curve::Ed25519,
+            "m/0'/1'/2'/2'/1000000000'",
+            &[
+                (
+                    "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+                    "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+                ),
+                (
+                    "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+                    "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+                ),
+                (
+                    "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+                    "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+                ),
+                (
+                    "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+                    "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+                ),
+                (
+                    "8f6d87f93d750e0efccda017d662a1b31a266e4a6f5993b15f5c1f07f74dd5cc",
+                    "30d1dc7e5fc04c31219ab25a27ae00b50f6fd66622f6e9c913253d6511d1e662",
+                ),
+                (
+                    "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230",
+                    "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793",
+                ),
+            ],
+        );
+    }
+
+    // Test vector 1 for secp256k1, from SLIP-0010 (and BIP32).
+    #[test]
+    fn test_slip10_secp256k1() {
+        check_chain(
+            Curve::Secp256k1,
+            "m/0'/1/2'/2/1000000000",
+            &[
+                (
+                    "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+                    "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+                ),
+                (
+                    "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                    "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+                ),
+                (
+                    "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                    "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+                ),
+                (
+                    "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+                    "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+                ),
+                (
+                    "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+                    "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+                ),
+                (
+                    "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+                    "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+                ),
+            ],
+        );
+    }
+
+    // Test vector 1 for nist256p1, from SLIP-0010.
+    #[test]
+    fn test_slip10_p256() {
+        check_chain(
+            Curve::P256,
+            "m/0'/1/2'/2/1000000000",
+            &[
+                (
+                    "beeb672fe4621673f722f38529c07392fecaa61015c80c34f29ce8b41b3cb6ea",
+                    "612091aaa12e22dd2abef664f8a01a82cae99ad7441b7ef8110424915c268bc2",
+                ),
+                (
+                    "3460cea53e6a6bb5fb391eeef3237ffd8724bf0a40e94943c98b83825342ee11",
+                    "6939694369114c67917a182c59ddb8cafc3004e63ca5d3b84403ba8613debc0c",
+                ),
+                (
+                    "4187afff1aafa8445010097fb99d23aee9f599450c7bd140b6826ac22ba21d0c",
+                    "284e9d38d07d21e4e281b645089a94f4cf5a5a81369acf151a1c3a57f18b2129",
+                ),
+                (
+                    "98c7514f562e64e74170cc3cf304ee1ce54d6b6da4f880f313e8204c2a185318",
+                    "694596e8a54f252c960eb771a3c41e7e32496d03b954aeb90f61635b8e092aa7",
+                ),
+                (
+                    "ba96f776a5c3907d7fd48bde5620ee374d4acfd540378476019eab70790c63a0",
+                    "5996c37fd3dd2679039b23ed6f70b506c6b56b3cb5e424681fb0fa64caf82aaa",
+                ),
+                (
+                    "b9b7b82d326bb9cb5b5b121066feea4eb93d5241103c9e7a18aad40f1dde8059",
+                    "21c4f269ef0a5fd1badf47eeacebeeaa3de22eb8e5b0adcd0f27dd99d34d0119",
+                ),
+            ],
+        );
+    }
+
+    // Derivation retry test vector for nist256p1, from SLIP-0010.
+    #[test]
+    fn test_slip10_p256_retry() {
+        let seed = hex::decode(SEED).unwrap();
+        let key = ExtendedSecretKey::from_seed(Curve::P256, &seed)
+            .unwrap()
+            .derive_path(&"m/28578'/33941".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            hex::encode(key.chain_code()),
+            "9e87fe95031f14736774cd82f25fd885065cb7c358c1edf813c72af535e83071"
+        );
+        assert_eq!(
+            hex::encode(key.key_bytes()),
+            "092154eed4af83e078ff9b84322015aefe5769e31270f62c3f66c33888335f3a"
+        );
+    }
+
+    #[test]
+    fn test_tezos_path() {
+        let mnemonic = bip39::Mnemonic::parse(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )
+        .unwrap();
+        let seed = mnemonic.to_seed("TREZOR");
+
+        let test = |curve, sk: &str| {
+            let key = ExtendedSecretKey::from_seed(curve, &seed)
+                .unwrap()
+                .derive_path(&DerivationPath::tezos(0).unwrap())
+                .unwrap();
+            assert_eq!(key.secret_key().to_base58_check(), sk);
+        };
+
+        test(
+            Curve::Ed25519,
+            "edsk4LvYQ15k7Bzb99gMLtSkiK1ZopKfR2cpzLkaVd2Ks9F75FVXcG",
+        );
+        test(
+            Curve::Secp256k1,
+            "spsk2nYhu1ryqfb7CktAp2aeo97BLHfEZeH3Yo1QXxLxCKsqWQtpfp",
+        );
+        test(
+            Curve::P256,
+            "p2sk3hD2r2b7xuEPTvYfc2hB6HcLkvmh7vocJteR8UFzYNW9AqqW62",
+        );
+    }
+
+    #[test]
+    fn test_unsupported() {
+        let seed = hex::decode(SEED).unwrap();
+        let key = ExtendedSecretKey::from_seed(Curve::Ed25519, &seed).unwrap();
+        assert!(matches!(
+            key.derive_child(1),
+            Err(CryptoError::Unsupported(_))
+        ));
+        assert!(matches!(
+            ExtendedSecretKey::from_seed(Curve::Bls, &seed),
+            Err(CryptoError::Unsupported("bls"))
+        ));
+    }
+
+    #[test]
+    fn test_derivation_path() {
+        let path: DerivationPath = "m/44'/1729'/0'/0'".parse().unwrap();
+        assert_eq!(Ok(path.clone()), DerivationPath::tezos(0));
+        assert_eq!(
+            DerivationPath::tezos(HARDENED - 1).unwrap().to_string(),
+            "m/44'/1729'/2147483647'/0'"
+        );
+        assert_eq!(
+            DerivationPath::tezos(HARDENED),
+            Err(DerivationPathError::InvalidIndex("2147483648".to_string()))
+        );
+        assert_eq!(path.to_string(), "m/44'/1729'/0'/0'");
+        assert_eq!("m/44h/1729h/0h/0h".parse(), Ok(path));
+
+        let path: DerivationPath = "m/0/2147483647'".parse().unwrap();
+        assert_eq!(path.indices(), &[0, u32::MAX]);
+        assert_eq!("m".parse(), Ok(DerivationPath::default()));
+
+        assert_eq!(
+            "44'/1729'".parse::<DerivationPath>(),
+            Err(DerivationPathError::MissingMaster)
+        );
+        for invalid in ["m/", "m/x", "m/1''", "m/+1", "m/01", "m/2147483648"] {
+            assert!(
+                matches!(
+                    invalid.parse::<DerivationPath>(),
+                    Err(DerivationPathError::InvalidIndex(_))
+                ),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
@@ -4,8 +4,8 @@
 
 use rand::{distributions::Alphanumeric, prelude::*, seq::SliceRandom};
 use std::iter;
-use tezos_encoding::binary_reader::BinaryReader;
-use tezos_encoding::encoding::{Encoding, Field};
+use tezos_data_encoding::binary_reader::BinaryReader;
+use tezos_data_encoding::encoding::{Encoding, Field};
 
 use honggfuzz::fuzz;
 use log::debug;
@@ -21,7 +21,7 @@ pub fn generate_bin_write_for_data(
         #[allow(clippy::unnecessary_cast)]
         #[allow(clippy::redundant_closure_call)]
         impl #impl_generics tezos_data_encoding::enc::BinWriter for #name #ty_generics #where_clause {
-            fn bin_write(&self, out: &mut Vec<u8>) -> tezos_data_encoding::enc::BinResult {
+            fn bin_write(&self, out: &mut tezos_data_encoding::__private::Vec<u8>) -> tezos_data_encoding::enc::BinResult {
                 #bin_write(self, out)
             }
         }
@@ -91,7 +91,7 @@ fn generate_struct_bin_write(encoding: &StructEncoding) -> TokenStream {
         fields_with_encoding.map(|f| generate_struct_field_bin_write(f.encoding().unwrap()));
     quote_spanned! {
         encoding.name.span()=>
-            (|data: &Self, out: &mut Vec<u8>| {
+            (|data: &Self, out: &mut tezos_data_encoding::__private::Vec<u8>| {
                 #(
                     tezos_data_encoding::enc::field(#field_name, #field_bin_write)(&data.#field, out)?;
                 )*
@@ -61,7 +61,7 @@ fn generate_struct_encoding(encoding: &StructEncoding) -> TokenStream {
     let name_str = encoding.name.to_string();
     let fields_encoding = encoding.fields.iter().filter_map(generate_field_encoding);
     quote_spanned! { encoding.name.span()=>
-        tezos_data_encoding::encoding::Encoding::Obj(#name_str, vec![
+        tezos_data_encoding::encoding::Encoding::Obj(#name_str, tezos_data_encoding::__private::vec![
             #(#fields_encoding),*
         ])
     }
@@ -84,8 +84,8 @@ fn generate_enum_encoding(encoding: &EnumEncoding) -> TokenStream {
     let tags_encoding = encoding.tags.iter().map(generate_tag_encoding);
     quote_spanned! { tag_type.span()=>
         tezos_data_encoding::encoding::Encoding::Tags(
-            std::mem::size_of::<#tag_type>(),
-            tezos_data_encoding::encoding::TagMap::new(vec![
+            ::core::mem::size_of::<#tag_type>(),
+            tezos_data_encoding::encoding::TagMap::new(tezos_data_encoding::__private::vec![
                 #(#tags_encoding),*
             ])
         )
@@ -94,7 +94,10 @@ fn generate_enum_encoding(encoding: &EnumEncoding) -> TokenStream {
 
 fn generate_tag_encoding(tag: &Tag) -> TokenStream {
     let id = &tag.id;
-    let name = tag.name.to_string();
+    let name = tag
+        .case_name
+        .as_ref()
+        .map_or_else(|| tag.name.to_string(), syn::LitStr::value);
     let encoding = generate_encoding(&tag.encoding);
     quote_spanned!(tag.name.span()=> tezos_data_encoding::encoding::Tag::new(#id, #name, #encoding))
 }
@@ -112,12 +115,12 @@ fn generate_list_encoding<'a>(
     span: Span,
 ) -> TokenStream {
     let encoding = generate_encoding(encoding);
-    size.as_ref().map_or_else(|| quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::List(Box::new(#encoding))), |size| quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::BoundedList(#size, Box::new(#encoding))))
+    size.as_ref().map_or_else(|| quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::List(tezos_data_encoding::__private::Box::new(#encoding))), |size| quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::BoundedList(#size, tezos_data_encoding::__private::Box::new(#encoding))))
 }
 
 fn generate_optional_field_encoding(encoding: &Encoding, span: Span) -> TokenStream {
     let encoding = generate_encoding(encoding);
-    quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::OptionalField(Box::new(#encoding)))
+    quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::OptionalField(tezos_data_encoding::__private::Box::new(#encoding)))
 }
 
 fn generate_sized_encoding<'a>(
@@ -126,7 +129,7 @@ fn generate_sized_encoding<'a>(
     span: Span,
 ) -> TokenStream {
     let encoding = generate_encoding(encoding);
-    quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::Sized(#size, Box::new(#encoding)))
+    quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::Sized(#size, tezos_data_encoding::__private::Box::new(#encoding)))
 }
 
 fn generate_bounded_encoding<'a>(
@@ -135,12 +138,12 @@ fn generate_bounded_encoding<'a>(
     span: Span,
 ) -> TokenStream {
     let encoding = generate_encoding(encoding);
-    quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::Bounded(#size, Box::new(#encoding)))
+    quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::Bounded(#size, tezos_data_encoding::__private::Box::new(#encoding)))
 }
 
 fn generate_short_dynamic_encoding(encoding: &Encoding, span: Span) -> TokenStream {
     let encoding = generate_encoding(encoding);
-    quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::ShortDynamic(Box::new(#encoding)))
+    quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::ShortDynamic(tezos_data_encoding::__private::Box::new(#encoding)))
 }
 
 fn generate_dynamic_encoding<'a>(
@@ -150,6 +153,6 @@ fn generate_dynamic_encoding<'a>(
 ) -> TokenStream {
     let encoding = generate_encoding(encoding);
     size.as_ref().map_or_else(
-        || quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::Dynamic(Box::new(#encoding))),
-        |size| quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::BoundedDynamic(#size, Box::new(#encoding))))
+        || quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::Dynamic(tezos_data_encoding::__private::Box::new(#encoding))),
+        |size| quote_spanned!(span=> tezos_data_encoding::encoding::Encoding::BoundedDynamic(#size, tezos_data_encoding::__private::Box::new(#encoding))))
 }
@@ -58,6 +58,7 @@ pub struct EnumEncoding<'a> {
 pub struct Tag<'a> {
     pub id: syn::LitInt,
     pub name: &'a syn::Ident,
+    pub case_name: Option<syn::LitStr>,
     pub encoding: Encoding<'a>,
 }
 
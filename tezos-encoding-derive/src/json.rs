@@ -0,0 +1,69 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+use crate::encoding::*;
+use proc_macro2::TokenStream;
+use quote::{quote_spanned, ToTokens};
+
+/// Rejects enums with two cases of the same fields: the JSON form of a case
+/// does not include its tag, so these cases could not be told apart.
+///
+/// Cases of different fields with the same JSON form are only detected when
+/// reading JSON.
+pub fn check_distinct_cases(input: &syn::DeriveInput) -> syn::Result<()> {
+    let variants = match &input.data {
+        syn::Data::Enum(data) => &data.variants,
+        _ => return Ok(()),
+    };
+    let cases: Vec<_> = variants
+        .iter()
+        .filter(|variant| !matches!(variant.fields, syn::Fields::Unit))
+        .map(|variant| (variant, variant.fields.to_token_stream().to_string()))
+        .collect();
+    for (i, (variant, fields)) in cases.iter().enumerate() {
+        if let Some((other, _)) = cases[..i].iter().find(|(_, other)| other == fields) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                format!(
+                    "cases `{}` and `{}` have the same JSON form",
+                    other.ident, variant.ident
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn generate_json_for_data(generics: &syn::Generics, data: &DataWithEncoding) -> TokenStream {
+    let name = data.name;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // `Deserialize` needs the extra `'de` lifetime.
+    let mut de_generics = generics.clone();
+    de_generics.params.insert(0, syn::parse_quote!('de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    quote_spanned! {
+        data.name.span()=>
+        impl #impl_generics tezos_data_encoding::json::HasJsonEncoding for #name #ty_generics #where_clause {}
+
+        impl #impl_generics tezos_data_encoding::__private::serde::Serialize for #name #ty_generics #where_clause {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: tezos_data_encoding::__private::serde::Serializer,
+            {
+                tezos_data_encoding::json::serialize(self, serializer)
+            }
+        }
+
+        impl #de_impl_generics tezos_data_encoding::__private::serde::Deserialize<'de> for #name #ty_generics #where_clause {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: tezos_data_encoding::__private::serde::Deserializer<'de>,
+            {
+                tezos_data_encoding::json::deserialize(deserializer)
+            }
+        }
+    }
+}
@@ -8,6 +8,9 @@
 //!
 //! Rather than manually implementing the `NomReader` and `BinWriter` traits, you can
 //! annotate your structures directly.
+//!
+//! `HasJsonEncoding` additionally implements serde's `Serialize` and `Deserialize`
+//! with the JSON form Octez uses for the same encoding.
 
 extern crate proc_macro;
 
@@ -17,6 +20,7 @@ use syn::{parse_macro_input, DeriveInput};
 mod bin;
 mod enc;
 mod encoding;
+mod json;
 mod make;
 mod nom;
 mod symbol;
@@ -53,3 +57,17 @@ pub fn derive_bin_writer(input: TokenStream) -> TokenStream {
     let tokens = crate::bin::generate_bin_write_for_data(&input.generics, &encoding);
     tokens.into()
 }
+
+#[proc_macro_derive(HasJsonEncoding, attributes(encoding))]
+pub fn derive_json_encoding(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let encoding = match crate::make::make_encoding(&input) {
+        Ok(encoding) => encoding,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    if let Err(e) = crate::json::check_distinct_cases(&input) {
+        return e.into_compile_error().into();
+    }
+    let tokens = crate::json::generate_json_for_data(&input.generics, &encoding);
+    tokens.into()
+}
@@ -155,6 +155,10 @@ fn make_basic_encoding_from_type<'a>(
         } else {
             unreachable!()
         }
+    } else if let Some(zarith) = get_attribute_no_param(meta, &symbol::Z_ARITH)? {
+        Encoding::Zarith(zarith.span)
+    } else if let Some(mutez) = get_attribute_no_param(meta, &symbol::MU_TEZ)? {
+        Encoding::MuTez(mutez.span)
     } else if let Some(mapped) = get_rust_to_primitive_mapping(ident) {
         // direct mapping from Rust type to encoding
         assert_builtin_encoding(meta, &mapped)?;
@@ -464,6 +468,12 @@ fn make_tag<'a>(
     meta: &mut Vec<syn::Meta>,
     default_id: &mut u16,
 ) -> Result<Tag<'a>> {
+    let case_name = get_attribute_value(meta, &symbol::NAME)?
+        .map(|lit| match lit {
+            syn::Lit::Str(str_lit) => Ok(str_lit),
+            _ => Err(error_spanned(lit, "String literal expected")),
+        })
+        .transpose()?;
     let id = get_attribute_value(meta, &symbol::TAG)?
         .map(|lit| {
             if let syn::Lit::Int(int_lit) = lit {
@@ -479,6 +489,7 @@ fn make_tag<'a>(
         })
         .unwrap_or_else(|| Ok(*default_id))?;
     *default_id = id + 1;
+    assert_empty_meta(meta)?;
     let name = &variant.ident;
     let encoding = match &variant.fields {
         syn::Fields::Named(_) => {
@@ -502,6 +513,7 @@ fn make_tag<'a>(
     Ok(Tag {
         id: syn::LitInt::new(&id.to_string(), variant.span()),
         name,
+        case_name,
         encoding,
     })
 }
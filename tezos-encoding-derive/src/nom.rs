@@ -258,7 +258,7 @@ fn generate_enum_nom_read(encoding: &EnumEncoding) -> TokenStream {
                         nom::Err::Error(
                             tezos_data_encoding::nom::error::DecodeError::#unknown_tag_error(
                                 input,
-                                format!("0x{:.2X}", tag)
+                                tezos_data_encoding::__private::format!("0x{:.2X}", tag)
                             )
                         )
                     );
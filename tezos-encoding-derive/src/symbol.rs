@@ -65,6 +65,8 @@ pub const SHORT_DYNAMIC: Symbol = Symbol("short_dynamic");
 pub const TAGS: Symbol = Symbol("tags");
 pub const IGNORE_UNKNOWN: Symbol = Symbol("ignore_unknown");
 pub const TAG: Symbol = Symbol("tag");
+/// Attribute used to specify the name of an enum case, used e.g. in JSON.
+pub const NAME: Symbol = Symbol("name");
 
 pub const Z_ARITH: Symbol = Symbol("zarith");
 pub const MU_TEZ: Symbol = Symbol("mutez");
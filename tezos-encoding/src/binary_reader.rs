@@ -2,12 +2,33 @@
 // SPDX-License-Identifier: MIT
 
 //! Tezos binary data reader.
+//!
+//! Decodes binary data into the intermediate [Value] form, driven by an
+//! [Encoding] rather than by a Rust type, so that data can be decoded even
+//! when no Rust type implementing [NomReader](crate::nom::NomReader) is available.
 
-use std::fmt;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
 
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take},
+    combinator::{all_consuming, map, success},
+    number::{complete::*, Endianness},
+    sequence::preceded,
+    Err,
+};
+use num_bigint::{BigInt, Sign};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::encoding::{Encoding, TagMap};
+use crate::nom::{self as enc_nom, error::DecodeError, NomInput, NomResult};
+use crate::types::{Value, BYTE_VAL_NONE, BYTE_VAL_SOME};
+
 /// Error produced by a [BinaryReader].
 #[cfg_attr(feature = "fuzzing", derive(fuzzcheck::DefaultMutator))]
 #[derive(Serialize, Deserialize, Debug, Clone, Error)]
@@ -24,3 +45,373 @@ impl fmt::Display for BinaryReaderError {
         }
     }
 }
+
+/// Decodes binary data into [Value], following an [Encoding].
+///
+/// ```rust
+/// use tezos_data_encoding::binary_reader::BinaryReader;
+/// use tezos_data_encoding::encoding::{Encoding, Field};
+/// use tezos_data_encoding::types::Value;
+///
+/// let encoding = Encoding::Obj(
+///     "Point",
+///     vec![Field::new("x", Encoding::Int16), Field::new("y", Encoding::Int16)],
+/// );
+/// let value = BinaryReader::new().read(&[0, 1, 0xff, 0xfe], &encoding).unwrap();
+///
+/// assert_eq!(
+///     value,
+///     Value::Record(vec![
+///         ("x".into(), Value::Int16(1)),
+///         ("y".into(), Value::Int16(-2)),
+///     ])
+/// );
+/// ```
+///
+/// Some encodings do not describe their binary form fully:
+/// - [Encoding::RangedInt] and [Encoding::RangedFloat] do not carry their range,
+///   and are read as 32-bit integers and as doubles;
+/// - [Encoding::Enum] does not carry its cases, and is read as a byte;
+/// - [Encoding::Custom] cannot be read, as it is defined by its Rust type only.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BinaryReader;
+
+impl BinaryReader {
+    pub fn new() -> Self {
+        BinaryReader
+    }
+
+    /// Decodes `data`, which must be consumed entirely, following `encoding`.
+    pub fn read(&self, data: &[u8], encoding: &Encoding) -> Result<Value, BinaryReaderError> {
+        all_consuming(|input| value(encoding, input))(data)
+            .map(|(_, value)| value)
+            .map_err(|error| read_error(data, error))
+    }
+}
+
+/// Converts a decoding error of `data` into a [BinaryReaderError].
+pub(crate) fn read_error(data: &[u8], error: Err<DecodeError<NomInput>>) -> BinaryReaderError {
+    match error {
+        Err::Error(error) | Err::Failure(error) => match error.get_unknown_tag() {
+            Some(tag) => BinaryReaderError::UnknownTag(tag.clone()),
+            None => BinaryReaderError::Error(enc_nom::error::convert_error(data, error)),
+        },
+        Err::Incomplete(_) => BinaryReaderError::Error("Incomplete input".to_string()),
+    }
+}
+
+/// Parses a [Value] following `encoding`, leaving the rest of the input.
+pub fn value<'a>(encoding: &Encoding, input: NomInput<'a>) -> NomResult<'a, Value> {
+    match encoding {
+        Encoding::Unit => Ok((input, Value::Unit)),
+        Encoding::Int8 => map(i8, Value::Int8)(input),
+        Encoding::Uint8 => map(u8, Value::Uint8)(input),
+        Encoding::Int16 => map(i16(Endianness::Big), Value::Int16)(input),
+        Encoding::Uint16 => map(u16(Endianness::Big), Value::Uint16)(input),
+        Encoding::Int31 => map(i32(Endianness::Big), Value::Int31)(input),
+        Encoding::Int32 => map(i32(Endianness::Big), Value::Int32)(input),
+        Encoding::Uint32 => map(u32(Endianness::Big), Value::Uint32)(input),
+        Encoding::Int64 | Encoding::Timestamp => map(i64(Endianness::Big), Value::Int64)(input),
+        Encoding::RangedInt => map(i32(Endianness::Big), Value::RangedInt)(input),
+        Encoding::Z => map(enc_nom::z_bignum, Value::Z)(input),
+        Encoding::Mutez => map(enc_nom::n_bignum, |n| {
+            Value::Mutez(BigInt::from_biguint(Sign::Plus, n))
+        })(input),
+        Encoding::Float => map(f64(Endianness::Big), Value::Float)(input),
+        Encoding::RangedFloat => map(f64(Endianness::Big), Value::RangedFloat)(input),
+        Encoding::Bool => map(enc_nom::boolean, Value::Bool)(input),
+        Encoding::String => map(enc_nom::string, Value::String)(input),
+        Encoding::BoundedString(max) => map(enc_nom::bounded_string(*max), Value::String)(input),
+        Encoding::Bytes | Encoding::Signature => map(enc_nom::bytes, Value::Bytes)(input),
+        Encoding::Hash(hash_type) => map(take(hash_type.size()), |bytes: &[u8]| {
+            Value::Bytes(bytes.to_vec())
+        })(input),
+        Encoding::Tags(tag_size, tags) => tagged(*tag_size, tags, input),
+        Encoding::List(encoding) => map(enc_nom::list(|i| value(encoding, i)), Value::List)(input),
+        Encoding::BoundedList(max, encoding) => map(
+            enc_nom::bounded_list(*max, |i| value(encoding, i)),
+            Value::List,
+        )(input),
+        Encoding::Enum => map(u8, |ordinal| Value::Enum(None, Some(ordinal.into())))(input),
+        Encoding::Option(encoding) => map(
+            alt((
+                preceded(tag([BYTE_VAL_NONE]), success(None)),
+                preceded(tag([BYTE_VAL_SOME]), map(|i| value(encoding, i), Some)),
+            )),
+            |value| Value::Option(value.map(Box::new)),
+        )(input),
+        Encoding::OptionalField(encoding) => {
+            map(enc_nom::optional_field(|i| value(encoding, i)), |value| {
+                Value::Option(value.map(Box::new))
+            })(input)
+        }
+        Encoding::Obj(_, fields) => {
+            let mut input = input;
+            let mut values = Vec::with_capacity(fields.len());
+            for field in fields {
+                let (rest, value) = value(field.get_encoding(), input)?;
+                values.push((field.get_name().clone(), value));
+                input = rest;
+            }
+            Ok((input, Value::Record(values)))
+        }
+        Encoding::Tup(encodings) => {
+            let mut input = input;
+            let mut values = Vec::with_capacity(encodings.len());
+            for encoding in encodings {
+                let (rest, value) = value(encoding, input)?;
+                values.push(value);
+                input = rest;
+            }
+            Ok((input, Value::Tuple(values)))
+        }
+        Encoding::ShortDynamic(encoding) => enc_nom::short_dynamic(|i| value(encoding, i))(input),
+        Encoding::Dynamic(encoding) => enc_nom::dynamic(|i| value(encoding, i))(input),
+        Encoding::BoundedDynamic(max, encoding) => {
+            enc_nom::bounded_dynamic(*max, |i| value(encoding, i))(input)
+        }
+        Encoding::Sized(size, encoding) => {
+            enc_nom::sized(*size, all_consuming(|i| value(encoding, i)))(input)
+        }
+        Encoding::Bounded(max, encoding) => enc_nom::bounded(*max, |i| value(encoding, i))(input),
+        Encoding::Greedy(encoding) => all_consuming(|i| value(encoding, i))(input),
+        Encoding::Custom => Err(Err::Error(DecodeError::unsupported(
+            input,
+            "Encoding::Custom",
+        ))),
+    }
+}
+
+fn tagged<'a>(tag_size: usize, tags: &TagMap, input: NomInput<'a>) -> NomResult<'a, Value> {
+    let (input, id) = match tag_size {
+        1 => map(u8, u16::from)(input)?,
+        2 => u16(Endianness::Big)(input)?,
+        _ => {
+            return Err(Err::Error(DecodeError::unsupported(
+                input,
+                "tag size other than 1 or 2 bytes",
+            )))
+        }
+    };
+    match tags.find_by_id(id) {
+        Some(tag) => {
+            let (input, inner) = value(tag.get_encoding(), input)?;
+            Ok((
+                input,
+                Value::Tag(tag.get_variant().clone(), Box::new(inner)),
+            ))
+        }
+        None => Err(Err::Error(DecodeError::unknown_tag(
+            input,
+            format!("0x{:.2X}", id),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enc::BinWriter;
+    use crate::encoding::{HasEncoding, Tag};
+    use crypto::address::Address;
+    use crypto::hash::{ContractKt1Hash, HashTrait};
+    use num_bigint::BigInt;
+
+    fn read(data: &[u8], encoding: &Encoding) -> Result<Value, BinaryReaderError> {
+        BinaryReader::new().read(data, encoding)
+    }
+
+    #[test]
+    fn read_primitives() {
+        let cases = [
+            (Encoding::Unit, vec![], Value::Unit),
+            (Encoding::Int8, vec![0xff], Value::Int8(-1)),
+            (Encoding::Uint8, vec![0xff], Value::Uint8(255)),
+            (Encoding::Int16, vec![0xff, 0xfe], Value::Int16(-2)),
+            (Encoding::Uint16, vec![0xff, 0xfe], Value::Uint16(65534)),
+            (Encoding::Int31, vec![0, 0, 1, 0], Value::Int31(256)),
+            (Encoding::Int32, vec![0xff; 4], Value::Int32(-1)),
+            (Encoding::Uint32, vec![0xff; 4], Value::Uint32(u32::MAX)),
+            (Encoding::Int64, vec![0xff; 8], Value::Int64(-1)),
+            (
+                Encoding::Timestamp,
+                vec![0, 0, 0, 0, 0x5e, 0, 0, 0],
+                Value::Int64(0x5e000000),
+            ),
+            (Encoding::RangedInt, vec![0, 0, 0, 7], Value::RangedInt(7)),
+            (
+                Encoding::Float,
+                1.5f64.to_be_bytes().to_vec(),
+                Value::Float(1.5),
+            ),
+            (
+                Encoding::RangedFloat,
+                1.5f64.to_be_bytes().to_vec(),
+                Value::RangedFloat(1.5),
+            ),
+            (Encoding::Bool, vec![0xff], Value::Bool(true)),
+            (Encoding::Bool, vec![0x00], Value::Bool(false)),
+            (Encoding::Z, vec![0xc1, 0x01], Value::Z(BigInt::from(-65))),
+            (
+                Encoding::Mutez,
+                vec![0x80, 0x01],
+                Value::Mutez(BigInt::from(128)),
+            ),
+            (
+                Encoding::String,
+                vec![0, 0, 0, 2, b'h', b'i'],
+                Value::String("hi".into()),
+            ),
+            (
+                Encoding::BoundedString(2),
+                vec![0, 0, 0, 2, b'h', b'i'],
+                Value::String("hi".into()),
+            ),
+            (Encoding::Bytes, vec![1, 2, 3], Value::Bytes(vec![1, 2, 3])),
+            (Encoding::Enum, vec![2], Value::Enum(None, Some(2))),
+        ];
+
+        for (encoding, data, expected) in cases {
+            assert_eq!(read(&data, &encoding).unwrap(), expected, "{:?}", encoding);
+        }
+    }
+
+    #[test]
+    fn read_combinators() {
+        let byte = || Box::new(Encoding::Uint8);
+        let cases = [
+            (Encoding::Option(byte()), vec![0], Value::Option(None)),
+            (
+                Encoding::Option(byte()),
+                vec![1, 7],
+                Value::Option(Some(Box::new(Value::Uint8(7)))),
+            ),
+            (
+                Encoding::OptionalField(byte()),
+                vec![0],
+                Value::Option(None),
+            ),
+            (
+                Encoding::OptionalField(byte()),
+                vec![0xff, 7],
+                Value::Option(Some(Box::new(Value::Uint8(7)))),
+            ),
+            (
+                Encoding::List(byte()),
+                vec![1, 2],
+                Value::List(vec![Value::Uint8(1), Value::Uint8(2)]),
+            ),
+            (
+                Encoding::BoundedList(2, byte()),
+                vec![1, 2],
+                Value::List(vec![Value::Uint8(1), Value::Uint8(2)]),
+            ),
+            (
+                Encoding::Tup(vec![Encoding::Uint8, Encoding::Bool]),
+                vec![1, 0],
+                Value::Tuple(vec![Value::Uint8(1), Value::Bool(false)]),
+            ),
+            (
+                Encoding::Dynamic(Box::new(Encoding::Bytes)),
+                vec![0, 0, 0, 1, 9],
+                Value::Bytes(vec![9]),
+            ),
+            (
+                Encoding::ShortDynamic(Box::new(Encoding::Bytes)),
+                vec![1, 9],
+                Value::Bytes(vec![9]),
+            ),
+            (
+                Encoding::BoundedDynamic(1, Box::new(Encoding::Bytes)),
+                vec![0, 0, 0, 1, 9],
+                Value::Bytes(vec![9]),
+            ),
+            (
+                Encoding::Tup(vec![Encoding::sized(2, Encoding::Bytes), Encoding::Uint8]),
+                vec![1, 2, 3],
+                Value::Tuple(vec![Value::Bytes(vec![1, 2]), Value::Uint8(3)]),
+            ),
+            (
+                Encoding::Tup(vec![
+                    Encoding::bounded(4, Encoding::Uint16),
+                    Encoding::Uint8,
+                ]),
+                vec![0, 1, 2],
+                Value::Tuple(vec![Value::Uint16(1), Value::Uint8(2)]),
+            ),
+            (
+                Encoding::greedy(Encoding::list(Encoding::Uint8)),
+                vec![1],
+                Value::List(vec![Value::Uint8(1)]),
+            ),
+        ];
+
+        for (encoding, data, expected) in cases {
+            assert_eq!(read(&data, &encoding).unwrap(), expected, "{:?}", encoding);
+        }
+    }
+
+    #[test]
+    fn read_tags() {
+        let encoding = Encoding::Tags(
+            2,
+            TagMap::new(vec![
+                Tag::new(0, "None", Encoding::Unit),
+                Tag::new(0x100, "Some", Encoding::Int8),
+            ]),
+        );
+        assert_eq!(
+            read(&[1, 0, 0xff], &encoding).unwrap(),
+            Value::Tag("Some".into(), Box::new(Value::Int8(-1)))
+        );
+        assert_eq!(
+            read(&[0, 0], &encoding).unwrap(),
+            Value::Tag("None".into(), Box::new(Value::Unit))
+        );
+        assert!(matches!(
+            read(&[0, 1], &encoding),
+            Err(BinaryReaderError::UnknownTag(tag)) if tag == "0x1"
+        ));
+    }
+
+    #[test]
+    fn read_errors() {
+        // Trailing data.
+        read(&[1, 2], &Encoding::Uint8).expect_err("");
+        // Missing data.
+        read(&[1], &Encoding::Uint16).expect_err("");
+        // Sized block not consumed entirely.
+        read(&[1, 2], &Encoding::sized(2, Encoding::Uint8)).expect_err("");
+        // Bounds.
+        read(&[0, 0, 0, 3, b'a', b'b', b'c'], &Encoding::BoundedString(2)).expect_err("");
+        read(&[1, 2, 3], &Encoding::bounded_list(2, Encoding::Uint8)).expect_err("");
+        read(
+            &[0, 0, 0, 2, 1, 2],
+            &Encoding::bounded_dynamic(1, Encoding::Bytes),
+        )
+        .expect_err("");
+        // Invalid boolean and option.
+        read(&[1], &Encoding::Bool).expect_err("");
+        read(&[2], &Encoding::option(Encoding::Unit)).expect_err("");
+        // No schema.
+        read(&[], &Encoding::Custom).expect_err("");
+    }
+
+    #[test]
+    fn read_address() {
+        let address = Address::Originated(ContractKt1Hash::try_from_bytes(&[5; 20]).unwrap());
+        let mut data = Vec::new();
+        address.bin_write(&mut data).unwrap();
+
+        assert_eq!(
+            read(&data, &Address::encoding()).unwrap(),
+            Value::Tag(
+                "Originated".into(),
+                Box::new(Value::Tuple(vec![
+                    Value::Bytes(vec![5; 20]),
+                    Value::Bytes(vec![0])
+                ]))
+            )
+        );
+    }
+}
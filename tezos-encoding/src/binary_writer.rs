@@ -2,5 +2,392 @@
 // SPDX-License-Identifier: MIT
 
 //! Tezos binary data writer.
+//!
+//! Encodes the intermediate [Value] form into binary data, driven by an
+//! [Encoding], producing the same bytes as the [BinWriter] of the Rust type
+//! with that encoding.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use num_bigint::Sign;
+
+use crate::enc::{self, BinError, BinResult, BinWriter};
+use crate::encoding::{Encoding, TagMap};
+use crate::types::{Value, Zarith, BYTE_FIELD_NONE, BYTE_FIELD_SOME, BYTE_VAL_NONE, BYTE_VAL_SOME};
 
 pub type BinaryWriterError = crate::enc::BinError;
+
+/// Encodes [Value] into binary data, following an [Encoding].
+///
+/// ```rust
+/// use tezos_data_encoding::binary_writer::BinaryWriter;
+/// use tezos_data_encoding::encoding::{Encoding, Field};
+/// use tezos_data_encoding::types::Value;
+///
+/// let encoding = Encoding::Obj(
+///     "Point",
+///     vec![Field::new("x", Encoding::Int16), Field::new("y", Encoding::Int16)],
+/// );
+/// let value = Value::Record(vec![
+///     ("x".into(), Value::Int16(1)),
+///     ("y".into(), Value::Int16(-2)),
+/// ]);
+///
+/// assert_eq!(
+///     BinaryWriter::new().write(&value, &encoding).unwrap(),
+///     vec![0, 1, 0xff, 0xfe]
+/// );
+/// ```
+///
+/// The value must have the shape of the encoding, as produced by
+/// [BinaryReader](crate::binary_reader::BinaryReader): the fields of a record
+/// are looked up by name, a missing optional field being absent, hashes are
+/// [Value::Bytes] of the size of the hash, timestamps are [Value::Int64], and
+/// enumerations are written from their ordinal number, on a byte.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BinaryWriter;
+
+impl BinaryWriter {
+    pub fn new() -> Self {
+        BinaryWriter
+    }
+
+    /// Encodes `value` following `encoding`.
+    pub fn write(&self, value: &Value, encoding: &Encoding) -> Result<Vec<u8>, BinaryWriterError> {
+        let mut out = Vec::new();
+        self::value(encoding, value, &mut out)?;
+        Ok(out)
+    }
+}
+
+fn mismatch(encoding: &Encoding, value: &Value) -> BinError {
+    BinError::custom(format!(
+        "Value {:?} does not match encoding {:?}",
+        value, encoding
+    ))
+}
+
+/// Writes `value` following `encoding` to `out`.
+pub fn value(encoding: &Encoding, value: &Value, out: &mut Vec<u8>) -> BinResult {
+    match (encoding, value) {
+        (Encoding::Unit, Value::Unit) => Ok(()),
+        (Encoding::Int8, Value::Int8(i)) => enc::i8(i, out),
+        (Encoding::Uint8, Value::Uint8(i)) => enc::u8(i, out),
+        (Encoding::Int16, Value::Int16(i)) => enc::i16(i, out),
+        (Encoding::Uint16, Value::Uint16(i)) => enc::u16(i, out),
+        (Encoding::Int31, Value::Int31(i)) => enc::i32(i, out),
+        (Encoding::Int32, Value::Int32(i)) => enc::i32(i, out),
+        (Encoding::Uint32, Value::Uint32(i)) => enc::u32(i, out),
+        (Encoding::Int64 | Encoding::Timestamp, Value::Int64(i)) => enc::i64(i, out),
+        (Encoding::RangedInt, Value::RangedInt(i)) => enc::i32(i, out),
+        (Encoding::Z, Value::Z(z)) => Zarith(z.clone()).bin_write(out),
+        (Encoding::Mutez, Value::Mutez(n)) if n.sign() != Sign::Minus => {
+            enc::n_bignum(n.magnitude(), out)
+        }
+        (Encoding::Float, Value::Float(f)) | (Encoding::RangedFloat, Value::RangedFloat(f)) => {
+            enc::put_bytes(&f.to_be_bytes(), out);
+            Ok(())
+        }
+        (Encoding::Bool, Value::Bool(b)) => enc::boolean(b, out),
+        (Encoding::String, Value::String(s)) => enc::string(s, out),
+        (Encoding::BoundedString(max), Value::String(s)) => enc::bounded_string(*max)(s, out),
+        (Encoding::Bytes | Encoding::Signature, Value::Bytes(bytes)) => enc::bytes(bytes, out),
+        (Encoding::Hash(hash_type), Value::Bytes(bytes)) => {
+            enc::sized(hash_type.size(), enc::bytes)(bytes, out)
+        }
+        (Encoding::Tags(tag_size, tags), Value::Tag(variant, value)) => {
+            tagged(*tag_size, tags, variant, value, out)
+        }
+        (Encoding::List(encoding), Value::List(values)) => {
+            enc::list(|v, out: &mut Vec<u8>| self::value(encoding, v, out))(values, out)
+        }
+        (Encoding::BoundedList(max, encoding), Value::List(values)) => enc::bounded_list(
+            *max,
+            |v, out: &mut Vec<u8>| self::value(encoding, v, out),
+        )(values, out),
+        (Encoding::Enum, Value::Enum(_, Some(ordinal))) => {
+            let ordinal = u8::try_from(*ordinal).map_err(|_| mismatch(encoding, value))?;
+            enc::u8(&ordinal, out)
+        }
+        (Encoding::Option(encoding), Value::Option(value)) => match value {
+            Some(value) => {
+                enc::put_byte(&BYTE_VAL_SOME, out);
+                self::value(encoding, value, out)
+            }
+            None => {
+                enc::put_byte(&BYTE_VAL_NONE, out);
+                Ok(())
+            }
+        },
+        (Encoding::OptionalField(encoding), Value::Option(value)) => match value {
+            Some(value) => {
+                enc::put_byte(&BYTE_FIELD_SOME, out);
+                self::value(encoding, value, out)
+            }
+            None => {
+                enc::put_byte(&BYTE_FIELD_NONE, out);
+                Ok(())
+            }
+        },
+        (Encoding::Obj(_, fields), Value::Record(values)) => fields.iter().try_for_each(|field| {
+            let name = field.get_name();
+            match values.iter().find(|(n, _)| n == name) {
+                Some((_, value)) => self::value(field.get_encoding(), value, out),
+                None if matches!(field.get_encoding(), Encoding::OptionalField(_)) => {
+                    enc::put_byte(&BYTE_FIELD_NONE, out);
+                    Ok(())
+                }
+                None => Err(BinError::custom(format!("Missing field `{}`", name))),
+            }
+        }),
+        (Encoding::Tup(encodings), Value::Tuple(values)) if encodings.len() == values.len() => {
+            encodings
+                .iter()
+                .zip(values)
+                .try_for_each(|(encoding, value)| self::value(encoding, value, out))
+        }
+        (Encoding::ShortDynamic(encoding), value) => {
+            enc::short_dynamic(|v, out: &mut Vec<u8>| self::value(encoding, v, out))(value, out)
+        }
+        (Encoding::Dynamic(encoding), value) => {
+            enc::dynamic(|v, out: &mut Vec<u8>| self::value(encoding, v, out))(value, out)
+        }
+        (Encoding::BoundedDynamic(max, encoding), value) => enc::bounded_dynamic(
+            *max,
+            |v, out: &mut Vec<u8>| self::value(encoding, v, out),
+        )(value, out),
+        (Encoding::Sized(size, encoding), value) => {
+            enc::sized(*size, |v, out: &mut Vec<u8>| self::value(encoding, v, out))(value, out)
+        }
+        (Encoding::Bounded(max, encoding), value) => {
+            enc::bounded(*max, |v, out: &mut Vec<u8>| self::value(encoding, v, out))(value, out)
+        }
+        (Encoding::Greedy(encoding), value) => self::value(encoding, value, out),
+        (Encoding::Custom, _) => Err(BinError::custom(format!(
+            "Encoding::Custom cannot be written from {:?}",
+            value
+        ))),
+        (encoding, value) => Err(mismatch(encoding, value)),
+    }
+}
+
+fn tagged(
+    tag_size: usize,
+    tags: &TagMap,
+    variant: &str,
+    value: &Value,
+    out: &mut Vec<u8>,
+) -> BinResult {
+    let tag = tags
+        .find_by_variant(variant)
+        .ok_or_else(|| BinError::custom(format!("Unknown variant `{}`", variant)))?;
+    match tag_size {
+        1 => {
+            let id = u8::try_from(tag.get_id())
+                .map_err(|_| BinError::custom(format!("Tag id {} is not a byte", tag.get_id())))?;
+            enc::u8(&id, out)?;
+        }
+        2 => enc::u16(&tag.get_id(), out)?,
+        _ => {
+            return Err(BinError::custom(format!(
+                "Unsupported tag size {}",
+                tag_size
+            )))
+        }
+    }
+    self::value(tag.get_encoding(), value, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_reader::BinaryReader;
+    use crate::encoding::{Field, HasEncoding, Tag};
+    use crate::types::Mutez;
+    use alloc::boxed::Box;
+    use alloc::string::String;
+    use crypto::address::Address;
+    use crypto::hash::{ContractKt1Hash, HashTrait};
+    use crypto::public_key_hash::PublicKeyHash;
+    use num_bigint::BigInt;
+    use proptest::prelude::*;
+
+    fn write(value: &Value, encoding: &Encoding) -> Result<Vec<u8>, BinaryWriterError> {
+        BinaryWriter::new().write(value, encoding)
+    }
+
+    fn assert_round_trip(data: &[u8], encoding: &Encoding) {
+        let value = BinaryReader::new().read(data, encoding).unwrap();
+        assert_eq!(write(&value, encoding).unwrap(), data, "{:?}", encoding);
+    }
+
+    #[test]
+    fn write_read_values() {
+        let byte = || Box::new(Encoding::Uint8);
+        let cases = [
+            (Encoding::Unit, vec![]),
+            (Encoding::Int8, vec![0xff]),
+            (Encoding::Uint16, vec![0xff, 0xfe]),
+            (Encoding::Int31, vec![0, 0, 1, 0]),
+            (Encoding::Uint32, vec![0xff; 4]),
+            (Encoding::Timestamp, vec![0, 0, 0, 0, 0x5e, 0, 0, 0]),
+            (Encoding::RangedInt, vec![0, 0, 0, 7]),
+            (Encoding::Float, 1.5f64.to_be_bytes().to_vec()),
+            (Encoding::Bool, vec![0xff]),
+            (Encoding::Z, vec![0xc1, 0x01]),
+            (Encoding::Mutez, vec![0x80, 0x01]),
+            (Encoding::BoundedString(2), vec![0, 0, 0, 2, b'h', b'i']),
+            (Encoding::Enum, vec![2]),
+            (Encoding::Option(byte()), vec![1, 7]),
+            (Encoding::OptionalField(byte()), vec![0]),
+            (Encoding::BoundedList(2, byte()), vec![1, 2]),
+            (
+                Encoding::ShortDynamic(Box::new(Encoding::Bytes)),
+                vec![1, 9],
+            ),
+            (
+                Encoding::bounded_dynamic(1, Encoding::Bytes),
+                vec![0, 0, 0, 1, 9],
+            ),
+            (
+                Encoding::Tup(vec![Encoding::sized(2, Encoding::Bytes), Encoding::Uint8]),
+                vec![1, 2, 3],
+            ),
+            (
+                Encoding::Tup(vec![
+                    Encoding::bounded(4, Encoding::Uint16),
+                    Encoding::Uint8,
+                ]),
+                vec![0, 1, 2],
+            ),
+            (Encoding::greedy(Encoding::list(Encoding::Uint8)), vec![1]),
+            (
+                Encoding::Tags(
+                    2,
+                    TagMap::new(vec![
+                        Tag::new(0, "None", Encoding::Unit),
+                        Tag::new(0x100, "Some", Encoding::Int8),
+                    ]),
+                ),
+                vec![1, 0, 0xff],
+            ),
+        ];
+
+        for (encoding, data) in cases {
+            assert_round_trip(&data, &encoding);
+        }
+    }
+
+    #[test]
+    fn write_same_as_bin_writer() {
+        let addresses = [
+            Address::Originated(ContractKt1Hash::try_from_bytes(&[5; 20]).unwrap()),
+            Address::Implicit(
+                PublicKeyHash::from_base58_check("tz1Ke2h7sDdakHJQh8WX4Z372du1KChsksyU").unwrap(),
+            ),
+        ];
+        for address in addresses {
+            let mut data = Vec::new();
+            address.bin_write(&mut data).unwrap();
+            assert_round_trip(&data, &Address::encoding());
+        }
+    }
+
+    #[test]
+    fn write_record_by_field_name() {
+        let encoding = Encoding::Obj(
+            "",
+            vec![
+                Field::new("a", Encoding::Uint8),
+                Field::new("b", Encoding::option_field(Encoding::Uint8)),
+                Field::new("c", Encoding::Uint8),
+            ],
+        );
+        let value = Value::Record(vec![
+            ("c".into(), Value::Uint8(3)),
+            ("a".into(), Value::Uint8(1)),
+        ]);
+        assert_eq!(write(&value, &encoding).unwrap(), vec![1, 0, 3]);
+
+        let value = Value::Record(vec![("a".into(), Value::Uint8(1))]);
+        write(&value, &encoding).expect_err("missing field");
+    }
+
+    #[test]
+    fn write_errors() {
+        write(&Value::Int8(1), &Encoding::Uint8).expect_err("");
+        write(&Value::Bytes(vec![0; 19]), &ContractKt1Hash::encoding()).expect_err("");
+        write(&Value::Mutez(BigInt::from(-1)), &Encoding::Mutez).expect_err("");
+        write(&Value::String("abc".into()), &Encoding::BoundedString(2)).expect_err("");
+        write(
+            &Value::List(vec![Value::Uint8(1); 3]),
+            &Encoding::bounded_list(2, Encoding::Uint8),
+        )
+        .expect_err("");
+        write(
+            &Value::Bytes(vec![1, 2, 3]),
+            &Encoding::sized(2, Encoding::Bytes),
+        )
+        .expect_err("");
+        write(
+            &Value::Tuple(vec![Value::Unit]),
+            &Encoding::Tup(vec![Encoding::Unit, Encoding::Unit]),
+        )
+        .expect_err("");
+        write(
+            &Value::Tag("Unknown".into(), Box::new(Value::Unit)),
+            &Address::encoding(),
+        )
+        .expect_err("");
+        write(&Value::Unit, &Encoding::Custom).expect_err("");
+    }
+
+    #[derive(Debug, HasEncoding, BinWriter)]
+    struct Derived {
+        i: i64,
+        z: Zarith,
+        n: Mutez,
+        #[encoding(string)]
+        s: String,
+        opt: Option<u16>,
+        #[encoding(dynamic, bytes)]
+        bytes: Vec<u8>,
+    }
+
+    proptest! {
+        #[test]
+        fn write_same_as_derived(
+            i in any::<i64>(),
+            z in any::<i128>(),
+            n in any::<u128>(),
+            s in any::<String>(),
+            bytes in any::<Vec<u8>>(),
+            opt in any::<Option<u16>>(),
+        ) {
+            let derived = Derived {
+                i,
+                z: Zarith(BigInt::from(z)),
+                n: Mutez(BigInt::from(n)),
+                s: s.clone(),
+                opt,
+                bytes: bytes.clone(),
+            };
+            let mut expected = Vec::new();
+            derived.bin_write(&mut expected).unwrap();
+
+            let value = Value::Record(vec![
+                ("i".into(), Value::Int64(i)),
+                ("z".into(), Value::Z(BigInt::from(z))),
+                ("n".into(), Value::Mutez(BigInt::from(n))),
+                ("s".into(), Value::String(s)),
+                ("opt".into(), Value::Option(opt.map(|o| Box::new(Value::Uint16(o))))),
+                ("bytes".into(), Value::Bytes(bytes)),
+            ]);
+
+            let encoding = Derived::encoding();
+            prop_assert_eq!(&write(&value, &encoding).unwrap(), &expected);
+            prop_assert_eq!(BinaryReader::new().read(&expected, &encoding).unwrap(), value);
+        }
+    }
+}
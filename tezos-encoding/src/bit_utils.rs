@@ -1,9 +1,11 @@
 // Copyright (c) SimpleStaking, Viable Systems and Tezedge Contributors
 // SPDX-License-Identifier: MIT
 
-use std::fmt::{Binary, Debug, Display, LowerHex, UpperHex};
-use std::mem::size_of;
-use std::ops::{BitAnd, BitAndAssign, BitOrAssign, Not, Shl, ShlAssign, Shr, ShrAssign};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Binary, Debug, Display, LowerHex, UpperHex};
+use core::mem::size_of;
+use core::ops::{BitAnd, BitAndAssign, BitOrAssign, Not, Shl, ShlAssign, Shr, ShrAssign};
 
 use bit_vec::BitVec;
 use thiserror::Error;
@@ -2,8 +2,12 @@
 // SPDX-CopyrightText: 2022-2023 TriliTech <contact@trili.tech>
 // SPDX-License-Identifier: MIT
 
-use std::convert::TryFrom;
-use std::fmt;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
 
 use crate::bit_utils::BitReverse;
 use crate::types::{Mutez, Zarith};
@@ -17,6 +21,7 @@ use thiserror::Error;
 /// Encoding error kind.
 pub enum BinErrorKind {
     /// I/O Error.
+    #[cfg(feature = "std")]
     #[error("I/O error: {0}")]
     IOError(std::io::Error),
     /// Boundary violation error, contains expected and actual sizes.
@@ -36,6 +41,7 @@ pub enum BinErrorKind {
 #[derive(Debug)]
 pub struct BinError(Vec<BinErrorKind>);
 
+#[cfg(feature = "std")]
 impl std::error::Error for BinError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         None
@@ -86,6 +92,7 @@ impl fmt::Display for BinError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for BinError {
     fn from(error: std::io::Error) -> Self {
         BinErrorKind::IOError(error).into()
@@ -297,6 +304,8 @@ pub fn boolean(b: &bool, out: &mut Vec<u8>) -> BinResult {
 
 // Rust integers encoding
 mod integers {
+    use alloc::vec::Vec;
+
     macro_rules! encode_integer {
         ($t:ident) => {
             pub fn $t(i: &$t, out: &mut Vec<u8>) -> super::BinResult {
@@ -350,7 +359,14 @@ encode_hash!(crypto::hash::PublicKeySecp256k1);
 encode_hash!(crypto::hash::PublicKeyP256);
 encode_hash!(crypto::hash::PublicKeyBls);
 encode_hash!(crypto::hash::SecretKeyEd25519);
+encode_hash!(crypto::hash::LegacySecretKeyEd25519);
+encode_hash!(crypto::hash::SecretKeySecp256k1);
+encode_hash!(crypto::hash::SecretKeyP256);
 encode_hash!(crypto::hash::SecretKeyBls);
+encode_hash!(crypto::hash::EncryptedSecretKeyEd25519);
+encode_hash!(crypto::hash::EncryptedSecretKeySecp256k1);
+encode_hash!(crypto::hash::EncryptedSecretKeyP256);
+encode_hash!(crypto::hash::EncryptedSecretKeyBls);
 encode_hash!(crypto::hash::UnknownSignature);
 encode_hash!(crypto::hash::Ed25519Signature);
 encode_hash!(crypto::hash::Secp256k1Signature);
@@ -365,6 +381,70 @@ impl BinWriter for crypto::signature::Signature {
     }
 }
 
+impl BinWriter for crypto::public_key::PublicKey {
+    fn bin_write(&self, out: &mut Vec<u8>) -> BinResult {
+        use crypto::public_key::PublicKey;
+
+        match self {
+            PublicKey::Ed25519(pk) => {
+                variant_with_field("PublicKey::Ed25519", u8, BinWriter::bin_write)(&0, pk, out)
+            }
+            PublicKey::Secp256k1(pk) => {
+                variant_with_field("PublicKey::Secp256k1", u8, BinWriter::bin_write)(&1, pk, out)
+            }
+            PublicKey::P256(pk) => {
+                variant_with_field("PublicKey::P256", u8, BinWriter::bin_write)(&2, pk, out)
+            }
+            PublicKey::Bls(pk) => {
+                variant_with_field("PublicKey::Bls", u8, BinWriter::bin_write)(&3, pk, out)
+            }
+        }
+    }
+}
+
+impl BinWriter for crypto::public_key_hash::PublicKeyHash {
+    fn bin_write(&self, out: &mut Vec<u8>) -> BinResult {
+        use crypto::public_key_hash::PublicKeyHash;
+
+        match self {
+            PublicKeyHash::Ed25519(h) => {
+                variant_with_field("PublicKeyHash::Ed25519", u8, BinWriter::bin_write)(&0, h, out)
+            }
+            PublicKeyHash::Secp256k1(h) => {
+                variant_with_field("PublicKeyHash::Secp256k1", u8, BinWriter::bin_write)(&1, h, out)
+            }
+            PublicKeyHash::P256(h) => {
+                variant_with_field("PublicKeyHash::P256", u8, BinWriter::bin_write)(&2, h, out)
+            }
+            PublicKeyHash::Bls(h) => {
+                variant_with_field("PublicKeyHash::Bls", u8, BinWriter::bin_write)(&3, h, out)
+            }
+        }
+    }
+}
+
+impl BinWriter for crypto::address::Address {
+    fn bin_write(&self, out: &mut Vec<u8>) -> BinResult {
+        use crypto::address::Address;
+
+        match self {
+            Address::Implicit(h) => {
+                variant_with_field("Address::Implicit", u8, BinWriter::bin_write)(&0, h, out)
+            }
+            Address::Originated(h) => {
+                variant_with_field("Address::Originated", u8, BinWriter::bin_write)(&1, h, out)?;
+                put_byte(&0, out);
+                Ok(())
+            }
+            Address::SmartRollup(h) => {
+                variant_with_field("Address::SmartRollup", u8, BinWriter::bin_write)(&3, h, out)?;
+                put_byte(&0, out);
+                Ok(())
+            }
+        }
+    }
+}
+
 impl BinWriter for Mutez {
     fn bin_write(&self, out: &mut Vec<u8>) -> BinResult {
         n_bignum(self.0.magnitude(), out)
@@ -529,31 +609,28 @@ pub fn optional_field<'a, T: 'a>(
 }
 
 pub fn n_bignum(n: &BigUint, out: &mut Vec<u8>) -> BinResult {
-    let bytes = n.to_bytes_be();
-    let mut d = 0;
-    let mut acc = 0;
-    for c in 0..bytes.len() {
-        let i = bytes.len() - c - 1;
-        let mut byte = acc | (bytes[i] << d) & 0x7f;
-        if d == 7 {
-            acc = 0;
-            d = 0;
-        } else {
-            let acc_d = 7 - d;
-            acc = bytes[i] >> acc_d;
-            d = 8 - acc_d;
-        }
-        if !(i == 0 && acc == 0) {
-            byte |= 0x80;
-        }
-        out.push(byte);
-    }
-    if acc != 0 {
-        out.push(acc);
-    }
+    // Little-endian groups of 7 bits, the most significant bit marking all but the last one.
+    let digits = n.to_radix_le(0x80);
+    let last = digits.len() - 1;
+    out.extend(
+        digits
+            .iter()
+            .enumerate()
+            .map(|(i, digit)| if i < last { digit | 0x80 } else { *digit }),
+    );
     Ok(())
 }
 
+/// Encodes a [Zarith] number, as used for fields with the `zarith` attribute.
+pub fn zarith(data: &Zarith, out: &mut Vec<u8>) -> BinResult {
+    data.bin_write(out)
+}
+
+/// Encodes a [Mutez] amount, as used for fields with the `mutez` attribute.
+pub fn mutez(data: &Mutez, out: &mut Vec<u8>) -> BinResult {
+    data.bin_write(out)
+}
+
 #[cfg(test)]
 mod test {
     use super::BinResult;
@@ -606,6 +683,7 @@ mod test {
             ("ffff", "ffff03"),
             ("10000", "808004"),
             ("10001", "818004"),
+            ("100000000000000", "808080808080808001"),
         ];
 
         use super::{BinWriter, Mutez};
@@ -4,8 +4,12 @@
 
 //! Schema used for serialization and deserialization.
 
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 use crypto::hash::{HashTrait, HashType};
-use std::collections::HashMap;
 
 pub use tezos_data_encoding_derive::HasEncoding;
 
@@ -65,14 +69,14 @@ impl Tag {
 
 #[derive(Debug, Clone)]
 pub struct TagMap {
-    id_to_tag: HashMap<u16, Tag>,
-    variant_to_id: HashMap<String, u16>,
+    id_to_tag: BTreeMap<u16, Tag>,
+    variant_to_id: BTreeMap<String, u16>,
 }
 
 impl TagMap {
     pub fn new(tags: Vec<Tag>) -> TagMap {
-        let mut id_to_tag = HashMap::new();
-        let mut variant_to_id = HashMap::new();
+        let mut id_to_tag = BTreeMap::new();
+        let mut variant_to_id = BTreeMap::new();
 
         for tag in tags {
             let tag_id = tag.get_id();
@@ -110,7 +114,7 @@ impl TagMap {
 /// Represents schema used for encoding a data into a json or a binary form.
 #[derive(Debug, Clone)]
 pub enum Encoding {
-    /// Encoded as nothing in binary or null in json
+    /// Encoded as nothing in binary and as an empty object in JSON.
     Unit,
     /// Signed 8 bit integer (data is encoded as a byte in binary and an integer in JSON).
     Int8,
@@ -216,6 +220,10 @@ pub enum Encoding {
     /// Decode various types of hashes. Hash has it's own predefined length and prefix.
     /// This is controller by a hash implementation.
     Hash(HashType),
+    /// Signature of any curve.
+    /// - encoded as its bytes in binary, taking all the available data like [Encoding::Bytes]
+    /// - encoded as a base58check string in JSON, `sig` or `BLsig` prefixed
+    Signature,
     /// Timestamp encoding.
     /// - encoded as RFC 3339 in json
     /// - encoded as [Encoding::Int64] in binary
@@ -337,7 +345,14 @@ hash_has_encoding!(PublicKeySecp256k1, PUBLIC_KEY_SECP256K1);
 hash_has_encoding!(PublicKeyP256, PUBLIC_KEY_P256);
 hash_has_encoding!(PublicKeyBls, PUBLIC_KEY_BLS);
 hash_has_encoding!(SecretKeyEd25519, SECRET_KEY_ED25519);
+hash_has_encoding!(LegacySecretKeyEd25519, LEGACY_SECRET_KEY_ED25519);
+hash_has_encoding!(SecretKeySecp256k1, SECRET_KEY_SECP256K1);
+hash_has_encoding!(SecretKeyP256, SECRET_KEY_P256);
 hash_has_encoding!(SecretKeyBls, SECRET_KEY_BLS);
+hash_has_encoding!(EncryptedSecretKeyEd25519, ENCRYPTED_SECRET_KEY_ED25519);
+hash_has_encoding!(EncryptedSecretKeySecp256k1, ENCRYPTED_SECRET_KEY_SECP256K1);
+hash_has_encoding!(EncryptedSecretKeyP256, ENCRYPTED_SECRET_KEY_P256);
+hash_has_encoding!(EncryptedSecretKeyBls, ENCRYPTED_SECRET_KEY_BLS);
 hash_has_encoding!(UnknownSignature, UNKNOWN_SIGNATURE);
 hash_has_encoding!(Ed25519Signature, ED25519_SIGNATURE_HASH);
 hash_has_encoding!(Secp256k1Signature, SECP256K1_SIGNATURE_HASH);
@@ -348,7 +363,64 @@ hash_has_encoding!(SmartRollupHash, SMART_ROLLUP_HASH);
 
 impl HasEncoding for crypto::signature::Signature {
     fn encoding() -> Encoding {
-        Encoding::Custom
+        Encoding::dynamic(Encoding::Signature)
+    }
+}
+
+impl HasEncoding for crypto::public_key::PublicKey {
+    fn encoding() -> Encoding {
+        use crypto::hash::{PublicKeyBls, PublicKeyEd25519, PublicKeyP256, PublicKeySecp256k1};
+
+        Encoding::Tags(
+            core::mem::size_of::<u8>(),
+            TagMap::new(vec![
+                Tag::new(0, "Ed25519", PublicKeyEd25519::encoding()),
+                Tag::new(1, "Secp256k1", PublicKeySecp256k1::encoding()),
+                Tag::new(2, "P256", PublicKeyP256::encoding()),
+                Tag::new(3, "Bls", PublicKeyBls::encoding()),
+            ]),
+        )
+    }
+}
+
+impl HasEncoding for crypto::public_key_hash::PublicKeyHash {
+    fn encoding() -> Encoding {
+        use crypto::hash::{ContractTz1Hash, ContractTz2Hash, ContractTz3Hash, ContractTz4Hash};
+
+        Encoding::Tags(
+            core::mem::size_of::<u8>(),
+            TagMap::new(vec![
+                Tag::new(0, "Ed25519", ContractTz1Hash::encoding()),
+                Tag::new(1, "Secp256k1", ContractTz2Hash::encoding()),
+                Tag::new(2, "P256", ContractTz3Hash::encoding()),
+                Tag::new(3, "Bls", ContractTz4Hash::encoding()),
+            ]),
+        )
+    }
+}
+
+impl HasEncoding for crypto::address::Address {
+    fn encoding() -> Encoding {
+        use crypto::hash::{ContractKt1Hash, SmartRollupHash};
+        use crypto::public_key_hash::PublicKeyHash;
+
+        let padding = || Encoding::Sized(1, Box::new(Encoding::Bytes));
+        Encoding::Tags(
+            core::mem::size_of::<u8>(),
+            TagMap::new(vec![
+                Tag::new(0, "Implicit", PublicKeyHash::encoding()),
+                Tag::new(
+                    1,
+                    "Originated",
+                    Encoding::Tup(vec![ContractKt1Hash::encoding(), padding()]),
+                ),
+                Tag::new(
+                    3,
+                    "SmartRollup",
+                    Encoding::Tup(vec![SmartRollupHash::encoding(), padding()]),
+                ),
+            ]),
+        )
     }
 }
 
@@ -114,7 +114,7 @@ impl Mutator<BigInt> for BigIntMutator {
         _max_cplx: f64,
     ) -> (Self::UnmutateToken, f64) {
         (
-            std::mem::replace(value, BigInt::from(self.rng.u64(..u64::MAX))),
+            core::mem::replace(value, BigInt::from(self.rng.u64(..u64::MAX))),
             BIGINT_COMPLEXITY,
         )
     }
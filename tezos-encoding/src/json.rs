@@ -0,0 +1,940 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! JSON form of data, driven by an [Encoding], as produced by Octez.
+//!
+//! The JSON form follows the rules of Octez `data_encoding`:
+//! - [Encoding::Unit] is an empty object;
+//! - [Encoding::Int64], [Encoding::Z] and [Encoding::Mutez] are decimal strings,
+//!   other numbers are JSON numbers;
+//! - [Encoding::Bytes] are hexadecimal strings, and [Encoding::Hash] and
+//!   [Encoding::Signature] are base58check strings;
+//! - [Encoding::Timestamp] is an RFC 3339 string, in UTC, or a decimal string
+//!   for timestamps outside of years 0 to 9999;
+//! - [Encoding::Option] is `null` when absent, and an absent [Encoding::OptionalField]
+//!   is omitted from its object;
+//! - [Encoding::Obj] is an object, [Encoding::Tup] and lists are arrays, except
+//!   for a hash followed by padding bytes (as in originated addresses), which is
+//!   the base58check string of the hash;
+//! - [Encoding::Tags] are the JSON form of their case, or the name of the case
+//!   for [Encoding::Unit] cases: when reading JSON, exactly one case must match,
+//!   as the tag itself is not part of the JSON form;
+//! - [Encoding::Enum] is its ordinal number: as the encoding does not list its
+//!   cases, a name is written when it is the only thing known, but never read;
+//! - size-related encodings ([Encoding::Dynamic], [Encoding::Sized], ...) are
+//!   the JSON form of the encoding they wrap.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crypto::signature::Signature;
+use num_bigint::{BigInt, Sign};
+use serde_json::{Map, Number, Value as Json};
+use thiserror::Error;
+
+use nom::combinator::all_consuming;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::binary_reader::{read_error, BinaryReader, BinaryReaderError};
+use crate::binary_writer::{BinaryWriter, BinaryWriterError};
+use crate::enc::BinWriter;
+use crate::encoding::{Encoding, HasEncoding};
+use crate::nom::NomReader;
+use crate::types::Value;
+
+pub use tezos_data_encoding_derive::HasJsonEncoding;
+
+/// Error converting to or from JSON.
+#[derive(Debug, Error)]
+pub enum JsonError {
+    /// The value or JSON does not have the shape of the encoding.
+    #[error("{0}")]
+    Mismatch(String),
+    /// Error decoding binary data.
+    #[error("{0}")]
+    Read(BinaryReaderError),
+    /// Error encoding binary data.
+    #[error("{0}")]
+    Write(BinaryWriterError),
+}
+
+impl From<BinaryReaderError> for JsonError {
+    fn from(error: BinaryReaderError) -> Self {
+        Self::Read(error)
+    }
+}
+
+impl From<BinaryWriterError> for JsonError {
+    fn from(error: BinaryWriterError) -> Self {
+        Self::Write(error)
+    }
+}
+
+/// The hash and the size of the padding of `Tup([Hash, Sized(size, Bytes)])`,
+/// whose JSON form is the one of the hash alone.
+pub(crate) fn padded_hash(encodings: &[Encoding]) -> Option<(&Encoding, usize)> {
+    match encodings {
+        [hash @ Encoding::Hash(_), Encoding::Sized(size, padding)]
+            if matches!(**padding, Encoding::Bytes) =>
+        {
+            Some((hash, *size))
+        }
+        _ => None,
+    }
+}
+
+fn mismatch<T: core::fmt::Debug>(what: T, encoding: &Encoding) -> JsonError {
+    JsonError::Mismatch(format!("{:?} does not match encoding {:?}", what, encoding))
+}
+
+/// Converts binary data into its JSON form, following `encoding`.
+pub fn binary_to_json(data: &[u8], encoding: &Encoding) -> Result<Json, JsonError> {
+    let value = BinaryReader::new().read(data, encoding)?;
+    to_json(&value, encoding)
+}
+
+/// Converts JSON into binary data, following `encoding`.
+pub fn json_to_binary(json: &Json, encoding: &Encoding) -> Result<Vec<u8>, JsonError> {
+    let value = from_json(json, encoding)?;
+    Ok(BinaryWriter::new().write(&value, encoding)?)
+}
+
+/// Types whose JSON form follows their [HasEncoding] encoding.
+///
+/// The JSON form is computed from the binary form of the value, so that it is
+/// the one Octez produces for the same encoding. Deriving it also implements
+/// serde's [Serialize] and [Deserialize] with that JSON form.
+///
+/// ```rust
+/// use tezos_crypto_rs::public_key_hash::PublicKeyHash;
+/// use tezos_data_encoding::enc::BinWriter;
+/// use tezos_data_encoding::encoding::HasEncoding;
+/// use tezos_data_encoding::json::HasJsonEncoding;
+/// use tezos_data_encoding::nom::NomReader;
+/// use tezos_data_encoding::types::Mutez;
+///
+/// #[derive(Debug, PartialEq, HasEncoding, NomReader, BinWriter, HasJsonEncoding)]
+/// #[encoding(tags = "u8")]
+/// enum Kind {
+///     #[encoding(tag = 1, name = "transaction")]
+///     Transfer,
+///     #[encoding(tag = 2, name = "delegation")]
+///     Delegation,
+/// }
+///
+/// #[derive(Debug, HasEncoding, NomReader, BinWriter, HasJsonEncoding)]
+/// struct Operation {
+///     kind: Kind,
+///     source: PublicKeyHash,
+///     #[encoding(mutez)]
+///     amount: Mutez,
+///     #[encoding(timestamp)]
+///     timestamp: i64,
+///     memo: Option<String>,
+/// }
+///
+/// let operation = Operation {
+///     kind: Kind::Transfer,
+///     source: PublicKeyHash::from_base58_check("tz1Ke2h7sDdakHJQh8WX4Z372du1KChsksyU").unwrap(),
+///     amount: Mutez(1_000_000.into()),
+///     timestamp: 0,
+///     memo: None,
+/// };
+///
+/// assert_eq!(
+///     serde_json::to_value(&operation).unwrap(),
+///     serde_json::json!({
+///         "kind": "transaction",
+///         "source": "tz1Ke2h7sDdakHJQh8WX4Z372du1KChsksyU",
+///         "amount": "1000000",
+///         "timestamp": "1970-01-01T00:00:00Z"
+///     })
+/// );
+///
+/// let json = serde_json::to_string(&operation).unwrap();
+/// let decoded: Operation = serde_json::from_str(&json).unwrap();
+/// assert_eq!(decoded.kind, Kind::Transfer);
+/// assert_eq!(decoded.amount.0, 1_000_000.into());
+/// ```
+///
+/// A case without fields is written as its name: the one given with the `name`
+/// attribute, as Octez case names usually differ from Rust variant names, or
+/// the variant name otherwise.
+///
+/// As in Octez, the JSON form of an enum is the one of its case, without its
+/// tag. Deriving it for two cases with the same fields is an error:
+///
+/// ```compile_fail
+/// # use tezos_data_encoding::enc::BinWriter;
+/// # use tezos_data_encoding::encoding::HasEncoding;
+/// # use tezos_data_encoding::json::HasJsonEncoding;
+/// # use tezos_data_encoding::nom::NomReader;
+/// #[derive(HasEncoding, NomReader, BinWriter, HasJsonEncoding)]
+/// struct Inner {
+///     x: u8,
+/// }
+///
+/// #[derive(HasEncoding, NomReader, BinWriter, HasJsonEncoding)]
+/// #[encoding(tags = "u8")]
+/// enum E {
+///     A(Inner),
+///     B(Inner),
+/// }
+/// ```
+///
+/// and cases whose JSON forms otherwise coincide cannot be read back:
+///
+/// ```rust
+/// # use tezos_data_encoding::enc::BinWriter;
+/// # use tezos_data_encoding::encoding::HasEncoding;
+/// # use tezos_data_encoding::json::HasJsonEncoding;
+/// # use tezos_data_encoding::nom::NomReader;
+/// #[derive(Debug, HasEncoding, NomReader, BinWriter, HasJsonEncoding)]
+/// struct Inner {
+///     x: u8,
+/// }
+///
+/// #[derive(Debug, HasEncoding, NomReader, BinWriter, HasJsonEncoding)]
+/// struct Other {
+///     x: u8,
+/// }
+///
+/// #[derive(Debug, HasEncoding, NomReader, BinWriter, HasJsonEncoding)]
+/// #[encoding(tags = "u8")]
+/// enum E {
+///     A(Inner),
+///     B(Other),
+/// }
+///
+/// let json = E::B(Other { x: 5 }).to_json().unwrap();
+/// assert_eq!(json, serde_json::json!({ "x": 5 }));
+/// assert!(E::from_json(&json).is_err());
+/// assert!(serde_json::from_value::<E>(json).is_err());
+/// ```
+pub trait HasJsonEncoding: HasEncoding + BinWriter + for<'a> NomReader<'a> {
+    /// Converts `self` into its JSON form.
+    fn to_json(&self) -> Result<Json, JsonError> {
+        let mut data = Vec::new();
+        self.bin_write(&mut data)?;
+        binary_to_json(&data, &Self::encoding())
+    }
+
+    /// Converts the JSON form of a value back into it.
+    fn from_json(json: &Json) -> Result<Self, JsonError> {
+        let data = json_to_binary(json, &Self::encoding())?;
+        let (_, value) = all_consuming(Self::nom_read)(&data)
+            .map_err(|error| JsonError::Read(read_error(&data, error)))?;
+        Ok(value)
+    }
+}
+
+/// Serializes `value` in its JSON form, used by derived [HasJsonEncoding]
+/// implementations.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: HasJsonEncoding,
+    S: Serializer,
+{
+    value
+        .to_json()
+        .map_err(ser::Error::custom)?
+        .serialize(serializer)
+}
+
+/// Deserializes a value from its JSON form, used by derived [HasJsonEncoding]
+/// implementations.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: HasJsonEncoding,
+    D: Deserializer<'de>,
+{
+    let json = Json::deserialize(deserializer)?;
+    T::from_json(&json).map_err(de::Error::custom)
+}
+
+/// Converts `value` into its JSON form, following `encoding`.
+pub fn to_json(value: &Value, encoding: &Encoding) -> Result<Json, JsonError> {
+    let json = match (encoding, value) {
+        (Encoding::Unit, Value::Unit) => Json::Object(Map::new()),
+        (Encoding::Int8, Value::Int8(i)) => Json::from(*i),
+        (Encoding::Uint8, Value::Uint8(i)) => Json::from(*i),
+        (Encoding::Int16, Value::Int16(i)) => Json::from(*i),
+        (Encoding::Uint16, Value::Uint16(i)) => Json::from(*i),
+        (Encoding::Int31, Value::Int31(i))
+        | (Encoding::Int32, Value::Int32(i))
+        | (Encoding::RangedInt, Value::RangedInt(i)) => Json::from(*i),
+        (Encoding::Uint32, Value::Uint32(i)) => Json::from(*i),
+        (Encoding::Int64, Value::Int64(i)) => Json::String(i.to_string()),
+        (Encoding::Timestamp, Value::Int64(i)) => Json::String(timestamp_to_string(*i)),
+        (Encoding::Z, Value::Z(z)) | (Encoding::Mutez, Value::Mutez(z)) => {
+            Json::String(z.to_string())
+        }
+        (Encoding::Float, Value::Float(f)) | (Encoding::RangedFloat, Value::RangedFloat(f)) => {
+            Number::from_f64(*f)
+                .map(Json::Number)
+                .ok_or_else(|| mismatch(value, encoding))?
+        }
+        (Encoding::Bool, Value::Bool(b)) => Json::Bool(*b),
+        (Encoding::String | Encoding::BoundedString(_), Value::String(s)) => {
+            Json::String(s.clone())
+        }
+        (Encoding::Bytes, Value::Bytes(bytes)) => Json::String(hex::encode(bytes)),
+        (Encoding::Hash(hash_type), Value::Bytes(bytes)) => Json::String(
+            hash_type
+                .hash_to_b58check(bytes)
+                .map_err(|_| mismatch(value, encoding))?,
+        ),
+        (Encoding::Signature, Value::Bytes(bytes)) => Json::String(
+            Signature::try_from(bytes.as_slice())
+                .map_err(|_| mismatch(value, encoding))?
+                .to_base58_check(),
+        ),
+        (Encoding::Tags(_, tags), Value::Tag(variant, value)) => {
+            let tag = tags
+                .find_by_variant(variant)
+                .ok_or_else(|| JsonError::Mismatch(format!("Unknown variant `{}`", variant)))?;
+            match tag.get_encoding() {
+                Encoding::Unit => Json::String(variant.clone()),
+                encoding => to_json(value, encoding)?,
+            }
+        }
+        (Encoding::List(encoding) | Encoding::BoundedList(_, encoding), Value::List(values)) => {
+            Json::Array(
+                values
+                    .iter()
+                    .map(|value| to_json(value, encoding))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        (Encoding::Enum, Value::Enum(_, Some(ordinal))) => Json::from(*ordinal),
+        (Encoding::Enum, Value::Enum(Some(name), None)) => Json::String(name.clone()),
+        (Encoding::Option(encoding) | Encoding::OptionalField(encoding), Value::Option(value)) => {
+            match value {
+                Some(value) => to_json(value, encoding)?,
+                None => Json::Null,
+            }
+        }
+        (Encoding::Obj(_, fields), Value::Record(values)) => {
+            let mut object = Map::new();
+            for field in fields {
+                let name = field.get_name();
+                match values.iter().find(|(n, _)| n == name) {
+                    Some((_, Value::Option(None)))
+                        if matches!(field.get_encoding(), Encoding::OptionalField(_)) => {}
+                    Some((_, value)) => {
+                        object.insert(name.clone(), to_json(value, field.get_encoding())?);
+                    }
+                    None if matches!(field.get_encoding(), Encoding::OptionalField(_)) => {}
+                    None => return Err(JsonError::Mismatch(format!("Missing field `{}`", name))),
+                }
+            }
+            Json::Object(object)
+        }
+        (Encoding::Tup(encodings), Value::Tuple(values)) if encodings.len() == values.len() => {
+            if let Some((hash, _)) = padded_hash(encodings) {
+                // The padding is not part of the JSON form, which is only
+                // faithful if it is the zeros read back by `from_json`.
+                return match &values[1] {
+                    Value::Bytes(padding) if padding.iter().all(|byte| *byte == 0) => {
+                        to_json(&values[0], hash)
+                    }
+                    padding => Err(JsonError::Mismatch(format!(
+                        "Non-zero padding {:?} of {:?}",
+                        padding, encoding
+                    ))),
+                };
+            }
+            Json::Array(
+                encodings
+                    .iter()
+                    .zip(values)
+                    .map(|(encoding, value)| to_json(value, encoding))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        (
+            Encoding::ShortDynamic(encoding)
+            | Encoding::Dynamic(encoding)
+            | Encoding::BoundedDynamic(_, encoding)
+            | Encoding::Sized(_, encoding)
+            | Encoding::Bounded(_, encoding)
+            | Encoding::Greedy(encoding),
+            value,
+        ) => to_json(value, encoding)?,
+        (encoding, value) => return Err(mismatch(value, encoding)),
+    };
+    Ok(json)
+}
+
+/// Converts JSON into [Value], following `encoding`.
+pub fn from_json(json: &Json, encoding: &Encoding) -> Result<Value, JsonError> {
+    fn int<T: TryFrom<i64>>(json: &Json, encoding: &Encoding) -> Result<T, JsonError> {
+        json.as_i64()
+            .and_then(|i| T::try_from(i).ok())
+            .ok_or_else(|| mismatch(json, encoding))
+    }
+
+    fn str<'a>(json: &'a Json, encoding: &Encoding) -> Result<&'a str, JsonError> {
+        json.as_str().ok_or_else(|| mismatch(json, encoding))
+    }
+
+    let value = match encoding {
+        Encoding::Unit => match json {
+            Json::Object(object) if object.is_empty() => Value::Unit,
+            _ => return Err(mismatch(json, encoding)),
+        },
+        Encoding::Int8 => Value::Int8(int(json, encoding)?),
+        Encoding::Uint8 => Value::Uint8(int(json, encoding)?),
+        Encoding::Int16 => Value::Int16(int(json, encoding)?),
+        Encoding::Uint16 => Value::Uint16(int(json, encoding)?),
+        Encoding::Int31 => Value::Int31(int(json, encoding)?),
+        Encoding::Int32 => Value::Int32(int(json, encoding)?),
+        Encoding::Uint32 => Value::Uint32(int(json, encoding)?),
+        Encoding::RangedInt => Value::RangedInt(int(json, encoding)?),
+        Encoding::Int64 => Value::Int64(
+            str(json, encoding)?
+                .parse()
+                .map_err(|_| mismatch(json, encoding))?,
+        ),
+        Encoding::Timestamp => Value::Int64(
+            str(json, encoding)
+                .ok()
+                .and_then(timestamp_from_str)
+                .ok_or_else(|| mismatch(json, encoding))?,
+        ),
+        Encoding::Z | Encoding::Mutez => {
+            let z: BigInt = str(json, encoding)?
+                .parse()
+                .map_err(|_| mismatch(json, encoding))?;
+            match encoding {
+                Encoding::Mutez if z.sign() == Sign::Minus => return Err(mismatch(json, encoding)),
+                Encoding::Mutez => Value::Mutez(z),
+                _ => Value::Z(z),
+            }
+        }
+        Encoding::Float | Encoding::RangedFloat => {
+            let f = json.as_f64().ok_or_else(|| mismatch(json, encoding))?;
+            match encoding {
+                Encoding::Float => Value::Float(f),
+                _ => Value::RangedFloat(f),
+            }
+        }
+        Encoding::Bool => Value::Bool(json.as_bool().ok_or_else(|| mismatch(json, encoding))?),
+        Encoding::String | Encoding::BoundedString(_) => {
+            Value::String(str(json, encoding)?.to_string())
+        }
+        Encoding::Bytes => {
+            Value::Bytes(hex::decode(str(json, encoding)?).map_err(|_| mismatch(json, encoding))?)
+        }
+        Encoding::Hash(hash_type) => Value::Bytes(
+            hash_type
+                .b58check_to_hash(str(json, encoding)?)
+                .map_err(|_| mismatch(json, encoding))?,
+        ),
+        Encoding::Signature => Value::Bytes(
+            Signature::from_base58_check(str(json, encoding)?)
+                .map_err(|_| mismatch(json, encoding))?
+                .into(),
+        ),
+        Encoding::Tags(_, tags) => {
+            let mut matches = tags.tags().filter_map(|tag| {
+                let value = match tag.get_encoding() {
+                    Encoding::Unit if json.as_str() == Some(tag.get_variant()) => Value::Unit,
+                    Encoding::Unit => return None,
+                    encoding => from_json(json, encoding).ok()?,
+                };
+                Some(Value::Tag(tag.get_variant().clone(), Box::new(value)))
+            });
+            match (matches.next(), matches.next()) {
+                (Some(value), None) => value,
+                (Some(Value::Tag(first, _)), Some(Value::Tag(second, _))) => {
+                    return Err(JsonError::Mismatch(format!(
+                        "{:?} matches both cases `{}` and `{}`",
+                        json, first, second
+                    )))
+                }
+                _ => return Err(mismatch(json, encoding)),
+            }
+        }
+        Encoding::List(inner) | Encoding::BoundedList(_, inner) => Value::List(
+            json.as_array()
+                .ok_or_else(|| mismatch(json, encoding))?
+                .iter()
+                .map(|json| from_json(json, inner))
+                .collect::<Result<_, _>>()?,
+        ),
+        Encoding::Enum => Value::Enum(None, Some(int(json, encoding)?)),
+        Encoding::Option(inner) | Encoding::OptionalField(inner) => match json {
+            Json::Null => Value::Option(None),
+            json => Value::Option(Some(Box::new(from_json(json, inner)?))),
+        },
+        Encoding::Obj(_, fields) => {
+            let object = json.as_object().ok_or_else(|| mismatch(json, encoding))?;
+            let mut values = Vec::with_capacity(fields.len());
+            for field in fields {
+                let name = field.get_name();
+                let value = match (object.get(name), field.get_encoding()) {
+                    (Some(json), encoding) => from_json(json, encoding)?,
+                    (None, Encoding::OptionalField(_)) => Value::Option(None),
+                    (None, _) => {
+                        return Err(JsonError::Mismatch(format!("Missing field `{}`", name)))
+                    }
+                };
+                values.push((name.clone(), value));
+            }
+            Value::Record(values)
+        }
+        Encoding::Tup(encodings) => match (padded_hash(encodings), json.as_array()) {
+            (Some((hash, size)), _) => {
+                Value::Tuple(vec![from_json(json, hash)?, Value::Bytes(vec![0; size])])
+            }
+            (None, Some(array)) if array.len() == encodings.len() => Value::Tuple(
+                array
+                    .iter()
+                    .zip(encodings)
+                    .map(|(json, encoding)| from_json(json, encoding))
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => return Err(mismatch(json, encoding)),
+        },
+        Encoding::ShortDynamic(inner)
+        | Encoding::Dynamic(inner)
+        | Encoding::BoundedDynamic(_, inner)
+        | Encoding::Sized(_, inner)
+        | Encoding::Bounded(_, inner)
+        | Encoding::Greedy(inner) => from_json(json, inner)?,
+        Encoding::Custom => return Err(mismatch(json, encoding)),
+    };
+    Ok(value)
+}
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+// Days since 1970-01-01 of a date of the proleptic Gregorian calendar.
+// See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// Inverse of [days_from_civil].
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn timestamp_to_string(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(SECONDS_PER_DAY);
+    let seconds = timestamp.rem_euclid(SECONDS_PER_DAY);
+    match civil_from_days(days) {
+        (year @ 0..=9999, month, day) => format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        ),
+        _ => timestamp.to_string(),
+    }
+}
+
+// Parses an RFC 3339 date, such as `2018-06-30T16:07:32Z`, or a decimal number of seconds.
+fn timestamp_from_str(s: &str) -> Option<i64> {
+    if let Ok(timestamp) = s.parse() {
+        return Some(timestamp);
+    }
+
+    fn number(s: &str, range: core::ops::RangeInclusive<i64>) -> Option<i64> {
+        if !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        s.parse().ok().filter(|n| range.contains(n))
+    }
+
+    let (date, time) = s.split_once(['T', 't', ' '])?;
+    let mut date = date.splitn(3, '-');
+    let year = date.next().filter(|y| y.len() == 4)?;
+    let year = number(year, 0..=9999)?;
+    let month = number(date.next()?, 1..=12)?;
+    let day = number(date.next()?, 1..=31)?;
+
+    let (time, offset) = match time.find(['Z', 'z', '+', '-']) {
+        Some(i) => time.split_at(i),
+        None => return None,
+    };
+    let offset = match offset {
+        "Z" | "z" => 0,
+        offset => {
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let (hours, minutes) = offset[1..].split_once(':')?;
+            sign * (number(hours, 0..=23)? * 3600 + number(minutes, 0..=59)? * 60)
+        }
+    };
+    // Fractions of a second are ignored.
+    let time = time.split('.').next()?;
+    let mut time = time.splitn(3, ':');
+    let hours = number(time.next()?, 0..=23)?;
+    let minutes = number(time.next()?, 0..=59)?;
+    let seconds = number(time.next()?, 0..=60)?;
+
+    Some(
+        days_from_civil(year, month, day) * SECONDS_PER_DAY + hours * 3600 + minutes * 60 + seconds
+            - offset,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enc::BinWriter;
+    use crate::encoding::{Field, HasEncoding, Tag, TagMap};
+    use crypto::address::Address;
+    use crypto::hash::{BlockHash, HashTrait, SmartRollupHash};
+    use crypto::public_key::PublicKey;
+    use serde_json::json;
+
+    fn round_trip(value: Value, encoding: &Encoding, expected: Json) {
+        let json = to_json(&value, encoding).unwrap();
+        assert_eq!(json, expected, "{:?}", encoding);
+        assert_eq!(from_json(&json, encoding).unwrap(), value, "{:?}", encoding);
+    }
+
+    #[test]
+    fn json_primitives() {
+        let cases = [
+            (Value::Unit, Encoding::Unit, json!({})),
+            (Value::Int8(-1), Encoding::Int8, json!(-1)),
+            (Value::Uint16(65535), Encoding::Uint16, json!(65535)),
+            (Value::Int31(-7), Encoding::Int31, json!(-7)),
+            (Value::Uint32(u32::MAX), Encoding::Uint32, json!(u32::MAX)),
+            (
+                Value::Int64(i64::MIN),
+                Encoding::Int64,
+                json!("-9223372036854775808"),
+            ),
+            (
+                Value::Z(BigInt::from(-1) << 100),
+                Encoding::Z,
+                json!("-1267650600228229401496703205376"),
+            ),
+            (
+                Value::Mutez(BigInt::from(1_000_000)),
+                Encoding::Mutez,
+                json!("1000000"),
+            ),
+            (Value::Float(1.5), Encoding::Float, json!(1.5)),
+            (Value::Bool(true), Encoding::Bool, json!(true)),
+            (
+                Value::String("tezos".into()),
+                Encoding::String,
+                json!("tezos"),
+            ),
+            (
+                Value::Bytes(vec![0xde, 0xad]),
+                Encoding::Bytes,
+                json!("dead"),
+            ),
+            (Value::Enum(None, Some(1)), Encoding::Enum, json!(1)),
+        ];
+
+        for (value, encoding, json) in cases {
+            round_trip(value, &encoding, json);
+        }
+    }
+
+    #[test]
+    fn json_hash() {
+        let hash = "BLockGenesisGenesisGenesisGenesisGenesisf79b5d1CoW2";
+        let bytes: Vec<u8> = BlockHash::from_b58check(hash).unwrap().into();
+        round_trip(Value::Bytes(bytes), &BlockHash::encoding(), json!(hash));
+
+        from_json(
+            &json!("KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi"),
+            &BlockHash::encoding(),
+        )
+        .expect_err("not a block hash");
+    }
+
+    #[test]
+    fn json_enum() {
+        assert_eq!(
+            to_json(&Value::Enum(Some("on".into()), Some(1)), &Encoding::Enum).unwrap(),
+            json!(1)
+        );
+        from_json(&json!("on"), &Encoding::Enum).expect_err("no case names");
+    }
+
+    #[test]
+    fn json_case_names() {
+        #[derive(Debug, PartialEq, HasEncoding, NomReader, BinWriter, HasJsonEncoding)]
+        #[encoding(tags = "u8")]
+        enum Kind {
+            #[encoding(tag = 108, name = "transaction")]
+            Transfer,
+            Origination,
+        }
+
+        assert_eq!(Kind::Transfer.to_json().unwrap(), json!("transaction"));
+        assert_eq!(Kind::Origination.to_json().unwrap(), json!("Origination"));
+        assert_eq!(
+            serde_json::from_value::<Kind>(json!("transaction")).unwrap(),
+            Kind::Transfer
+        );
+        serde_json::from_value::<Kind>(json!("Transfer")).expect_err("not the case name");
+
+        // The name only changes the JSON form.
+        let mut binary = Vec::new();
+        Kind::Origination.bin_write(&mut binary).unwrap();
+        assert_eq!(binary, [109]);
+    }
+
+    #[test]
+    fn json_address() {
+        // As in the `destination` of Octez transactions.
+        let kt1 = "KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi";
+        let binary = json_to_binary(&json!(kt1), &Address::encoding()).unwrap();
+        let mut expected = Vec::new();
+        Address::from_base58_check(kt1)
+            .unwrap()
+            .bin_write(&mut expected)
+            .unwrap();
+        assert_eq!(binary, expected);
+        assert_eq!(
+            binary_to_json(&binary, &Address::encoding()).unwrap(),
+            json!(kt1)
+        );
+
+        let sr1 = Address::SmartRollup(SmartRollupHash::try_from_bytes(&[7; 20]).unwrap());
+        let mut binary = Vec::new();
+        sr1.bin_write(&mut binary).unwrap();
+        let json = binary_to_json(&binary, &Address::encoding()).unwrap();
+        assert_eq!(json, json!(sr1.to_base58_check()));
+        assert_eq!(json_to_binary(&json, &Address::encoding()).unwrap(), binary);
+
+        let tz1 = "tz1Ke2h7sDdakHJQh8WX4Z372du1KChsksyU";
+        let binary = json_to_binary(&json!(tz1), &Address::encoding()).unwrap();
+        assert_eq!(
+            binary_to_json(&binary, &Address::encoding()).unwrap(),
+            json!(tz1)
+        );
+
+        // Non-zero padding would be lost in JSON.
+        let mut binary = json_to_binary(&json!(kt1), &Address::encoding()).unwrap();
+        *binary.last_mut().unwrap() = 1;
+        binary_to_json(&binary, &Address::encoding()).expect_err("non-zero padding");
+    }
+
+    #[test]
+    fn json_signed_operation() {
+        #[derive(Debug, PartialEq, HasEncoding, NomReader, BinWriter, HasJsonEncoding)]
+        struct Signed {
+            level: i32,
+            signature: Signature,
+        }
+
+        let sigs = [
+            "sigNCaj9CnmD94eZH9C7aPPqBbVCJF72fYmCFAXqEbWfqE633WNFWYQJFnDUFgRUQXR8fQ5tKSfJeTe6UAi75eTzzQf7AEc1",
+            "BLsig9ZQAvgzs2W3zLWWYcb4MpARP2jJhdg2DgcKXffHda81qKTnMyM1F2GrrwQRR44tTu3P4qgEPeDvCpijEzrEbTu8UuLpgrcXWbz9KBQWQfmFVgkjKF9Je6aNdRQ2ju782iMLUciaPo",
+        ];
+        for sig in sigs {
+            let signed = Signed {
+                level: 7,
+                signature: Signature::from_base58_check(sig).unwrap(),
+            };
+            let json = json!({ "level": 7, "signature": sig });
+            assert_eq!(serde_json::to_value(&signed).unwrap(), json);
+            assert_eq!(serde_json::from_value::<Signed>(json).unwrap(), signed);
+        }
+
+        // Signatures of a given curve are read back as generic ones.
+        let edsig = Signature::from_base58_check(sigs[0])
+            .unwrap()
+            .try_into()
+            .map(Signature::Ed25519)
+            .unwrap();
+        let json = Signed {
+            level: 7,
+            signature: edsig,
+        }
+        .to_json()
+        .unwrap();
+        assert_eq!(json["signature"], json!(sigs[0]));
+
+        from_json(
+            &json!("edpkuBknW28nW72KG6RoHtYW7p12T6GKc7nAbwYX5m8Wd9sDVC9yav"),
+            &Encoding::Signature,
+        )
+        .expect_err("not a signature");
+    }
+
+    #[test]
+    fn json_timestamp() {
+        let cases = [
+            (0, "1970-01-01T00:00:00Z"),
+            (-1, "1969-12-31T23:59:59Z"),
+            (1_530_374_852, "2018-06-30T16:07:32Z"),
+            (951_782_400, "2000-02-29T00:00:00Z"),
+            (253_402_300_799, "9999-12-31T23:59:59Z"),
+            (253_402_300_800, "253402300800"),
+        ];
+        for (timestamp, json) in cases {
+            round_trip(Value::Int64(timestamp), &Encoding::Timestamp, json!(json));
+        }
+
+        let parse = |s| from_json(&json!(s), &Encoding::Timestamp).ok();
+        assert_eq!(
+            parse("2018-06-30T18:07:32.25+02:00"),
+            Some(Value::Int64(1_530_374_852))
+        );
+        assert_eq!(parse("2018-06-30T16:07:32"), None);
+        assert_eq!(parse("2018-13-30T16:07:32Z"), None);
+    }
+
+    #[test]
+    fn json_combinators() {
+        let encoding = Encoding::Obj(
+            "",
+            vec![
+                Field::new("level", Encoding::Int32),
+                Field::new("option", Encoding::option(Encoding::Uint8)),
+                Field::new("absent", Encoding::option_field(Encoding::Uint8)),
+                Field::new("present", Encoding::option_field(Encoding::Uint8)),
+                Field::new(
+                    "list",
+                    Encoding::dynamic(Encoding::list(Encoding::sized(2, Encoding::Bytes))),
+                ),
+                Field::new(
+                    "tuple",
+                    Encoding::Tup(vec![Encoding::Bool, Encoding::Int16]),
+                ),
+            ],
+        );
+        let value = Value::Record(vec![
+            ("level".into(), Value::Int32(42)),
+            ("option".into(), Value::Option(None)),
+            ("absent".into(), Value::Option(None)),
+            (
+                "present".into(),
+                Value::Option(Some(Box::new(Value::Uint8(1)))),
+            ),
+            (
+                "list".into(),
+                Value::List(vec![Value::Bytes(vec![1, 2]), Value::Bytes(vec![3, 4])]),
+            ),
+            (
+                "tuple".into(),
+                Value::Tuple(vec![Value::Bool(false), Value::Int16(-1)]),
+            ),
+        ]);
+        round_trip(
+            value,
+            &encoding,
+            json!({
+                "level": 42,
+                "option": null,
+                "present": 1,
+                "list": ["0102", "0304"],
+                "tuple": [false, -1],
+            }),
+        );
+
+        from_json(&json!({ "level": 42 }), &encoding).expect_err("missing fields");
+    }
+
+    #[test]
+    fn json_tags() {
+        let encoding = Encoding::Tags(
+            1,
+            TagMap::new(vec![
+                Tag::new(0, "Number", Encoding::Int16),
+                Tag::new(1, "Empty", Encoding::Unit),
+                Tag::new(2, "Flag", Encoding::Bool),
+            ]),
+        );
+        round_trip(
+            Value::Tag("Empty".into(), Box::new(Value::Unit)),
+            &encoding,
+            json!("Empty"),
+        );
+        round_trip(
+            Value::Tag("Flag".into(), Box::new(Value::Bool(true))),
+            &encoding,
+            json!(true),
+        );
+        round_trip(
+            Value::Tag("Number".into(), Box::new(Value::Int16(1))),
+            &encoding,
+            json!(1),
+        );
+        from_json(&json!("a"), &encoding).expect_err("no matching case");
+    }
+
+    #[test]
+    fn json_tags_same_shape() {
+        let encoding = Encoding::Tags(
+            1,
+            TagMap::new(vec![
+                Tag::new(0, "A", Encoding::Int16),
+                Tag::new(1, "B", Encoding::Int16),
+            ]),
+        );
+        let value = Value::Tag("B".into(), Box::new(Value::Int16(5)));
+        let json = to_json(&value, &encoding).unwrap();
+        assert_eq!(json, json!(5));
+        match from_json(&json, &encoding) {
+            Err(JsonError::Mismatch(error)) => assert!(error.contains("`A` and `B`"), "{}", error),
+            result => panic!("ambiguous cases read as {:?}", result),
+        }
+
+        // A string case only clashes with a unit case for the name of the latter.
+        let encoding = Encoding::Tags(
+            1,
+            TagMap::new(vec![
+                Tag::new(0, "Empty", Encoding::Unit),
+                Tag::new(1, "Text", Encoding::String),
+            ]),
+        );
+        round_trip(
+            Value::Tag("Text".into(), Box::new(Value::String("a".into()))),
+            &encoding,
+            json!("a"),
+        );
+        from_json(&json!("Empty"), &encoding).expect_err("ambiguous cases");
+    }
+
+    #[test]
+    fn json_binary() {
+        let pk = "edpkuBknW28nW72KG6RoHtYW7p12T6GKc7nAbwYX5m8Wd9sDVC9yav";
+        let encoding = PublicKey::encoding();
+
+        let binary = json_to_binary(&json!(pk), &encoding).unwrap();
+        let mut expected = Vec::new();
+        crate::enc::BinWriter::bin_write(&PublicKey::from_base58_check(pk).unwrap(), &mut expected)
+            .unwrap();
+        assert_eq!(binary, expected);
+        assert_eq!(binary_to_json(&binary, &encoding).unwrap(), json!(pk));
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MIT
 #![forbid(unsafe_code)]
 #![cfg_attr(feature = "fuzzing", feature(no_coverage))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
 //! This crate provides serialization and deserialization functionality for the data types used by the Tezos shell.
 //!
@@ -49,7 +50,11 @@
 //! # assert_eq!(outer, result);
 //! ```
 
+extern crate alloc;
 extern crate tezos_crypto_rs as crypto;
+// Lets the unit tests use the derives, whose output refers to this crate by name.
+#[cfg(test)]
+extern crate self as tezos_data_encoding;
 
 mod bit_utils;
 pub mod types;
@@ -59,7 +64,20 @@ pub mod binary_writer;
 
 pub mod enc;
 pub mod encoding;
+pub mod json;
 pub mod nom;
+pub mod schema;
 
 #[cfg(feature = "fuzzing")]
 pub mod fuzzing;
+
+/// Items used by the derived implementations, which must build with and
+/// without std.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::boxed::Box;
+    pub use alloc::format;
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+    pub use serde;
+}
@@ -2,6 +2,9 @@
 // SPDX-CopyrightText: 2022-2023 TriliTech <contact@trili.tech>
 // SPDX-License-Identifier: MIT
 
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use bitvec::slice::BitSlice;
 use bitvec::{bitvec, order::Msb0, view::BitView};
 use crypto::hash::HashTrait;
@@ -23,7 +26,9 @@ use crate::types::{Mutez, Zarith};
 use self::error::{BoundedEncodingKind, DecodeError, DecodeErrorKind};
 
 pub mod error {
-    use std::{fmt::Write, str::Utf8Error};
+    use alloc::boxed::Box;
+    use alloc::string::String;
+    use core::{fmt::Write, str::Utf8Error};
 
     use nom::{
         error::{ErrorKind, FromExternalError},
@@ -64,6 +69,8 @@ pub mod error {
         UnknownTag(String),
         /// Invalid tag
         InvalidTag(String),
+        /// Encoding that cannot be decoded without its Rust type
+        Unsupported(&'static str),
     }
 
     /// Specific bounded encoding kind.
@@ -117,6 +124,14 @@ pub mod error {
             }
         }
 
+        pub fn unsupported(input: NomInput<'a>, encoding: &'static str) -> Self {
+            Self {
+                input,
+                kind: DecodeErrorKind::Unsupported(encoding),
+                other: None,
+            }
+        }
+
         pub fn get_unknown_tag(&self) -> Option<&String> {
             match self.kind {
                 DecodeErrorKind::UnknownTag(ref tag) => Some(tag),
@@ -195,6 +210,9 @@ pub mod error {
             DecodeErrorKind::Bits(e) => write!(res, " while performing bits operation: {}", e),
             DecodeErrorKind::UnknownTag(tag) => write!(res, " caused by unsupported tag `{}`", tag),
             DecodeErrorKind::InvalidTag(tag) => write!(res, " caused by invalid tag `{}`", tag),
+            DecodeErrorKind::Unsupported(encoding) => {
+                write!(res, " caused by unsupported encoding `{}`", encoding)
+            }
         };
 
         if let Some(other) = error.other {
@@ -253,7 +271,14 @@ hash_nom_reader!(PublicKeySecp256k1);
 hash_nom_reader!(PublicKeyP256);
 hash_nom_reader!(PublicKeyBls);
 hash_nom_reader!(SecretKeyEd25519);
+hash_nom_reader!(LegacySecretKeyEd25519);
+hash_nom_reader!(SecretKeySecp256k1);
+hash_nom_reader!(SecretKeyP256);
 hash_nom_reader!(SecretKeyBls);
+hash_nom_reader!(EncryptedSecretKeyEd25519);
+hash_nom_reader!(EncryptedSecretKeySecp256k1);
+hash_nom_reader!(EncryptedSecretKeyP256);
+hash_nom_reader!(EncryptedSecretKeyBls);
 hash_nom_reader!(UnknownSignature);
 hash_nom_reader!(Ed25519Signature);
 hash_nom_reader!(Secp256k1Signature);
@@ -276,6 +301,102 @@ impl<'a> NomReader<'a> for crypto::signature::Signature {
     }
 }
 
+impl<'a> NomReader<'a> for crypto::public_key::PublicKey {
+    fn nom_read(input: &'a [u8]) -> NomResult<'a, Self> {
+        use crypto::hash::{PublicKeyBls, PublicKeyEd25519, PublicKeyP256, PublicKeySecp256k1};
+        use crypto::public_key::PublicKey;
+
+        let (input, tag) = u8(input)?;
+        match tag {
+            0 => map(
+                variant("PublicKey::Ed25519", PublicKeyEd25519::nom_read),
+                PublicKey::Ed25519,
+            )(input),
+            1 => map(
+                variant("PublicKey::Secp256k1", PublicKeySecp256k1::nom_read),
+                PublicKey::Secp256k1,
+            )(input),
+            2 => map(
+                variant("PublicKey::P256", PublicKeyP256::nom_read),
+                PublicKey::P256,
+            )(input),
+            3 => map(
+                variant("PublicKey::Bls", PublicKeyBls::nom_read),
+                PublicKey::Bls,
+            )(input),
+            _ => Err(Err::Error(DecodeError::invalid_tag(
+                input,
+                format!("0x{:.2X}", tag),
+            ))),
+        }
+    }
+}
+
+impl<'a> NomReader<'a> for crypto::public_key_hash::PublicKeyHash {
+    fn nom_read(input: &'a [u8]) -> NomResult<'a, Self> {
+        use crypto::hash::{ContractTz1Hash, ContractTz2Hash, ContractTz3Hash, ContractTz4Hash};
+        use crypto::public_key_hash::PublicKeyHash;
+
+        let (input, tag) = u8(input)?;
+        match tag {
+            0 => map(
+                variant("PublicKeyHash::Ed25519", ContractTz1Hash::nom_read),
+                PublicKeyHash::Ed25519,
+            )(input),
+            1 => map(
+                variant("PublicKeyHash::Secp256k1", ContractTz2Hash::nom_read),
+                PublicKeyHash::Secp256k1,
+            )(input),
+            2 => map(
+                variant("PublicKeyHash::P256", ContractTz3Hash::nom_read),
+                PublicKeyHash::P256,
+            )(input),
+            3 => map(
+                variant("PublicKeyHash::Bls", ContractTz4Hash::nom_read),
+                PublicKeyHash::Bls,
+            )(input),
+            _ => Err(Err::Error(DecodeError::invalid_tag(
+                input,
+                format!("0x{:.2X}", tag),
+            ))),
+        }
+    }
+}
+
+impl<'a> NomReader<'a> for crypto::address::Address {
+    fn nom_read(input: &'a [u8]) -> NomResult<'a, Self> {
+        use crypto::address::Address;
+        use crypto::hash::{ContractKt1Hash, SmartRollupHash};
+        use crypto::public_key_hash::PublicKeyHash;
+
+        let (input, tag) = u8(input)?;
+        match tag {
+            0 => map(
+                variant("Address::Implicit", PublicKeyHash::nom_read),
+                Address::Implicit,
+            )(input),
+            1 => map(
+                variant(
+                    "Address::Originated",
+                    terminated(ContractKt1Hash::nom_read, padding),
+                ),
+                Address::Originated,
+            )(input),
+            3 => map(
+                variant(
+                    "Address::SmartRollup",
+                    terminated(SmartRollupHash::nom_read, padding),
+                ),
+                Address::SmartRollup,
+            )(input),
+            _ => Err(Err::Error(DecodeError::invalid_tag(
+                input,
+                format!("0x{:.2X}", tag),
+            ))),
+        }
+    }
+}
+
 impl<'a> NomReader<'a> for Zarith {
     fn nom_read(bytes: &[u8]) -> NomResult<Self> {
         map(z_bignum, |big_int| big_int.into())(bytes)
@@ -290,6 +411,12 @@ impl<'a> NomReader<'a> for Mutez {
     }
 }
 
+/// Skips a single padding byte, whatever its value.
+#[inline(always)]
+pub fn padding(input: NomInput) -> NomResult<()> {
+    map(take(1_u8), |_| ())(input)
+}
+
 /// Reads a boolean value.
 #[inline(always)]
 pub fn boolean(input: NomInput) -> NomResult<bool> {
@@ -335,7 +462,7 @@ fn bounded_size(kind: BoundedEncodingKind, max: usize) -> impl FnMut(NomInput) -
 #[inline(always)]
 pub fn string(input: NomInput) -> NomResult<String> {
     map_res(complete(length_data(size)), |bytes| {
-        std::str::from_utf8(bytes).map(str::to_string)
+        core::str::from_utf8(bytes).map(str::to_string)
     })(input)
 }
 
@@ -345,7 +472,7 @@ pub fn string(input: NomInput) -> NomResult<String> {
 pub fn bounded_string<'a>(max: usize) -> impl FnMut(NomInput<'a>) -> NomResult<'a, String> {
     map_res(
         complete(length_data(bounded_size(BoundedEncodingKind::String, max))),
-        |bytes| std::str::from_utf8(bytes).map(str::to_string),
+        |bytes| core::str::from_utf8(bytes).map(str::to_string),
     )
 }
 
@@ -456,11 +583,11 @@ where
     O: Clone,
 {
     move |input: NomInput| {
-        let max = std::cmp::min(max, input.input_len());
-        let bounded = input.slice(std::ops::RangeTo { end: max });
+        let max = core::cmp::min(max, input.input_len());
+        let bounded = input.slice(core::ops::RangeTo { end: max });
         match f.parse(bounded) {
             Ok((rest, parsed)) => Ok((
-                input.slice(std::ops::RangeFrom {
+                input.slice(core::ops::RangeFrom {
                     start: max - rest.input_len(),
                 }),
                 parsed,
@@ -487,7 +614,7 @@ where
 {
     move |input| {
         let input_len = input.len();
-        let reserved_len = input_len - std::cmp::min(input_len, size);
+        let reserved_len = input_len - core::cmp::min(input_len, size);
         let reserved_input = &input[..reserved_len];
         let (reserved_input, out) = parser(reserved_input)?;
         Ok((&input[reserved_len - reserved_input.len()..], out))
@@ -566,6 +693,18 @@ pub fn n_bignum(mut input: NomInput) -> NomResult<BigUint> {
     Ok((input, BigUint::from_bytes_be(&bitvec.into_vec())))
 }
 
+/// Reads a [Zarith] number, as used for fields with the `zarith` attribute.
+#[inline(always)]
+pub fn zarith(input: NomInput) -> NomResult<Zarith> {
+    Zarith::nom_read(input)
+}
+
+/// Reads a [Mutez] amount, as used for fields with the `mutez` attribute.
+#[inline(always)]
+pub fn mutez(input: NomInput) -> NomResult<Mutez> {
+    Mutez::nom_read(input)
+}
+
 pub fn hashed<'a, O, F>(mut parser: F) -> impl FnMut(NomInput<'a>) -> NomResult<'a, (O, Vec<u8>)>
 where
     F: FnMut(NomInput<'a>) -> NomResult<'a, O>,
@@ -838,6 +977,147 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_public_key() {
+        use crate::enc::BinWriter;
+        use crypto::public_key::PublicKey;
+
+        let data = [
+            (
+                "edpkuBknW28nW72KG6RoHtYW7p12T6GKc7nAbwYX5m8Wd9sDVC9yav",
+                "004798d2cc98473d7e250c898885718afd2e4efbcb1a1595ab9730761ed830de0f",
+            ),
+            (
+                "sppk7bgK42i4MqXdSToddBHCmVgUvQqgkLvpgpocGnGPtGYhi4FS2DQ",
+                "0103389c1b59d648e1366da27f1d881d2b0ac205549238fab30933ea6cea1d51982c",
+            ),
+            (
+                "p2pk653ADWvBsiKHXJEGx5VbsQzVHvjTfwfJoy5fKCALiQQK9q91oLk",
+                "02023d4cd695866ac1d075fe3f0c844b48787ed9fa53393a797d1b7646337b973d5f",
+            ),
+            (
+                "BLpk1mJXuRWVxRJRkUES7E16u4KeKMGibFtR995FxSFzeyMm8ckngdo4Cx4P3KWeRQ1NeY3iEWwq",
+                "0382bee1e51d83b420ec0ce0844fe2975bafcf5dc948986cacc7075a82a3845512c45835c0c6fe2b174e2a33afbe032705",
+            ),
+        ];
+
+        for (b58, enc) in data {
+            let pk = PublicKey::from_base58_check(b58).unwrap();
+            let input = hex::decode(enc).unwrap();
+            let (rest, dec) = PublicKey::nom_read(&input).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(dec, pk);
+
+            let mut out = Vec::new();
+            pk.bin_write(&mut out).unwrap();
+            assert_eq!(out, input);
+        }
+
+        let input = hex::decode("04").unwrap();
+        assert_eq!(
+            PublicKey::nom_read(&input),
+            Err(Err::Error(DecodeError::invalid_tag(
+                &input[1..],
+                "0x4".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_public_key_hash() {
+        use crate::enc::BinWriter;
+        use crypto::public_key_hash::PublicKeyHash;
+
+        let data = [
+            (
+                "tz1KqTpEZ7Yob7QbPE4Hy4Wo8fHG8LhKxZSx",
+                "0002298c03ed7d454a101eb7022bc95f7e5f41ac78",
+            ),
+            (
+                "tz2TRpDZTo2uWH1BuiQD58pmywTmD1kt6NWt",
+                "01d1a8e57e38da4561e98d250fb946d62735145263",
+            ),
+            (
+                "tz3V4z2s9854NWrWqX7ndmAUB558AdDB8XMw",
+                "025fdfe6e54f724cc284ff9c38c7ef2d3ce2d40ae4",
+            ),
+            (
+                "tz4NPp9xHJMgoRQwE2iL66NnfeBcnEoskeuj",
+                "0392ddf981151db604334a4166c3a443934bd8bc71",
+            ),
+        ];
+
+        for (b58, enc) in data {
+            let pkh = PublicKeyHash::from_base58_check(b58).unwrap();
+            let input = hex::decode(enc).unwrap();
+            let (rest, dec) = PublicKeyHash::nom_read(&input).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(dec, pkh);
+
+            let mut out = Vec::new();
+            pkh.bin_write(&mut out).unwrap();
+            assert_eq!(out, input);
+        }
+
+        let input = hex::decode("ff").unwrap();
+        assert_eq!(
+            PublicKeyHash::nom_read(&input),
+            Err(Err::Error(DecodeError::invalid_tag(
+                &input[1..],
+                "0xFF".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_address() {
+        use crate::enc::BinWriter;
+        use crypto::address::Address;
+
+        let data = [
+            (
+                "tz1KqTpEZ7Yob7QbPE4Hy4Wo8fHG8LhKxZSx",
+                "000002298c03ed7d454a101eb7022bc95f7e5f41ac78",
+            ),
+            (
+                "tz4NPp9xHJMgoRQwE2iL66NnfeBcnEoskeuj",
+                "000392ddf981151db604334a4166c3a443934bd8bc71",
+            ),
+            (
+                "KT1BEqzn5Wx8uJrZNvuS9DVHmLvG9td3fDLi",
+                "011d23c1d3d2f8a4ea5e8784b8f7ecf2ad304c0fe600",
+            ),
+            (
+                "sr1UNDWPUYVeomgG15wn5jSw689EJ4RNnVQa",
+                "03f4e47cb3c43a68b0d48e3094092ca42d713addb500",
+            ),
+        ];
+
+        for (b58, enc) in data {
+            let address = Address::from_base58_check(b58).unwrap();
+            let input = hex::decode(enc).unwrap();
+            let (rest, dec) = Address::nom_read(&input).unwrap();
+            assert!(rest.is_empty());
+            assert_eq!(dec, address);
+
+            let mut out = Vec::new();
+            address.bin_write(&mut out).unwrap();
+            assert_eq!(out, input);
+        }
+
+        let input = hex::decode("011d23c1d3d2f8a4ea5e8784b8f7ecf2ad304c0fe6").unwrap();
+        Address::nom_read(&input).expect_err("Error is expected");
+
+        let input = hex::decode("02").unwrap();
+        assert_eq!(
+            Address::nom_read(&input),
+            Err(Err::Error(DecodeError::invalid_tag(
+                &input[1..],
+                "0x2".to_string()
+            )))
+        );
+    }
+
     fn hex_to_bigint(s: &str) -> BigInt {
         num_bigint::BigInt::from_i64(i64::from_str_radix(s, 16).unwrap()).unwrap()
     }
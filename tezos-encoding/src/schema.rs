@@ -0,0 +1,770 @@
+// SPDX-FileCopyrightText: 2023-2024 Trilitech <contact@trili.tech>
+//
+// SPDX-License-Identifier: MIT
+
+//! Descriptions of an [Encoding], in the formats produced by `octez-codec describe`.
+//!
+//! - [binary_schema] is the machine-readable description of the binary form,
+//!   as the JSON form of Octez `Binary_schema.t`;
+//! - [json_schema] is the JSON Schema (draft 04) of the JSON form, as produced
+//!   by the [json](crate::json) module.
+//!
+//! Octez names the descriptions it refers to after its own definitions, while
+//! here [Encoding::Obj] descriptions are named after the Rust type and other
+//! ones after the field they are used in. Encodings with no equivalent in
+//! Octez ([Encoding::Enum], whose cases are unknown, and [Encoding::Custom])
+//! are described as an 8-bit integer and as bytes respectively.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use serde_json::{json, Map, Value as Json};
+
+use crate::encoding::{Encoding, TagMap};
+use crate::json::padded_hash;
+
+/// Size of the data of a binary field, as described by Octez.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// Always the given number of bytes.
+    Fixed(usize),
+    /// Its size can be determined from the data itself.
+    Dynamic,
+    /// Takes the rest of the input.
+    Variable,
+}
+
+impl Kind {
+    fn to_json(self) -> Json {
+        match self {
+            // Octez uses the `Float` constant for fixed size kinds.
+            Kind::Fixed(size) => json!({ "size": size, "kind": "Float" }),
+            Kind::Dynamic => json!({ "kind": "Dynamic" }),
+            Kind::Variable => json!({ "kind": "Variable" }),
+        }
+    }
+
+    fn sequence(kinds: impl IntoIterator<Item = Kind>) -> Kind {
+        kinds
+            .into_iter()
+            .fold(Kind::Fixed(0), |acc, kind| match (acc, kind) {
+                (Kind::Fixed(a), Kind::Fixed(b)) => Kind::Fixed(a + b),
+                (Kind::Variable, _) | (_, Kind::Variable) => Kind::Variable,
+                _ => Kind::Dynamic,
+            })
+    }
+}
+
+fn kind(encoding: &Encoding) -> Kind {
+    match encoding {
+        Encoding::Unit => Kind::Fixed(0),
+        Encoding::Int8 | Encoding::Uint8 | Encoding::Bool | Encoding::Enum => Kind::Fixed(1),
+        Encoding::Int16 | Encoding::Uint16 => Kind::Fixed(2),
+        Encoding::Int31 | Encoding::Int32 | Encoding::Uint32 | Encoding::RangedInt => {
+            Kind::Fixed(4)
+        }
+        Encoding::Int64 | Encoding::Timestamp | Encoding::Float | Encoding::RangedFloat => {
+            Kind::Fixed(8)
+        }
+        Encoding::Hash(hash_type) => Kind::Fixed(hash_type.size()),
+        Encoding::Sized(size, _) => Kind::Fixed(*size),
+        Encoding::Z
+        | Encoding::Mutez
+        | Encoding::String
+        | Encoding::BoundedString(_)
+        | Encoding::ShortDynamic(_)
+        | Encoding::Dynamic(_)
+        | Encoding::BoundedDynamic(_, _) => Kind::Dynamic,
+        Encoding::Bytes
+        | Encoding::Signature
+        | Encoding::List(_)
+        | Encoding::BoundedList(_, _)
+        | Encoding::Greedy(_)
+        | Encoding::Custom => Kind::Variable,
+        Encoding::Bounded(_, encoding) => kind(encoding),
+        Encoding::Option(encoding) | Encoding::OptionalField(encoding) => match kind(encoding) {
+            Kind::Variable => Kind::Variable,
+            _ => Kind::Dynamic,
+        },
+        Encoding::Obj(_, fields) => Kind::sequence(fields.iter().map(|f| kind(f.get_encoding()))),
+        Encoding::Tup(encodings) => Kind::sequence(encodings.iter().map(kind)),
+        Encoding::Tags(tag_size, tags) => {
+            let mut kinds = tags.tags().map(|tag| kind(tag.get_encoding()));
+            let first = kinds.next().unwrap_or(Kind::Fixed(0));
+            match kinds.try_fold(first, |acc, kind| match (acc, kind) {
+                (Kind::Fixed(a), Kind::Fixed(b)) if a == b => Some(acc),
+                (Kind::Variable, _) | (_, Kind::Variable) => None,
+                _ => Some(Kind::Dynamic),
+            }) {
+                Some(Kind::Fixed(size)) => Kind::Fixed(tag_size + size),
+                Some(_) => Kind::Dynamic,
+                None => Kind::Variable,
+            }
+        }
+    }
+}
+
+fn integer_size(tag_size: usize) -> &'static str {
+    if tag_size == 1 {
+        "Uint8"
+    } else {
+        "Uint16"
+    }
+}
+
+fn int_layout(size: &str) -> Json {
+    json!({ "size": size, "kind": "Int" })
+}
+
+fn field_json(name: Option<&str>, layout: Json, kind: Kind) -> Json {
+    match name {
+        Some(name) => json!({
+            "name": name,
+            "layout": layout,
+            "data_kind": kind.to_json(),
+            "kind": "named",
+        }),
+        None => json!({ "layout": layout, "kind": "anon", "data_kind": kind.to_json() }),
+    }
+}
+
+fn dynamic_size_json(name: Option<&str>, size: &str, num_fields: usize) -> Json {
+    let mut json = json!({ "kind": "dyn", "num_fields": num_fields, "size": size });
+    if let Some(name) = name {
+        json["name"] = Json::from(name);
+    }
+    json
+}
+
+const Z_DESCRIPTION: &str = "A variable-length sequence of bytes encoding a Zarith integer. \
+    Each byte has a running unary size bit: the most significant bit of each byte indicates \
+    whether this is the last byte in the sequence (0) or whether the sequence continues (1). \
+    The second most significant bit of the first byte is reserved for the sign (0 for \
+    positive, 1 for negative). Size and sign bits ignored, the data is the binary \
+    representation of the absolute value of the number in little-endian order.";
+
+const N_DESCRIPTION: &str = "A variable-length sequence of bytes encoding a Zarith natural \
+    number. Each byte has a running unary size bit: the most significant bit of each byte \
+    indicates whether this is the last byte in the sequence (0) or whether the sequence \
+    continues (1). Size bits ignored, the data is the binary representation of the number in \
+    little-endian order.";
+
+/// Description of the binary form of `encoding`, as the JSON form of an Octez
+/// `Binary_schema.t`.
+///
+/// ```rust
+/// use tezos_data_encoding::encoding::{Encoding, Field};
+/// use tezos_data_encoding::schema::binary_schema;
+///
+/// let encoding = Encoding::Obj("point", vec![Field::new("x", Encoding::Int16)]);
+///
+/// assert_eq!(
+///     binary_schema(&encoding),
+///     serde_json::json!({
+///         "toplevel": {
+///             "fields": [{
+///                 "name": "x",
+///                 "layout": { "size": "Int16", "kind": "Int" },
+///                 "data_kind": { "size": 2, "kind": "Float" },
+///                 "kind": "named"
+///             }]
+///         },
+///         "fields": []
+///     })
+/// );
+/// ```
+pub fn binary_schema(encoding: &Encoding) -> Json {
+    let mut describer = BinaryDescriber::default();
+    let toplevel = describer.toplevel(encoding);
+    let fields: Vec<Json> = describer
+        .references
+        .into_iter()
+        .map(|reference| {
+            let mut description = json!({ "title": reference.title });
+            if let Some(text) = reference.description {
+                description["description"] = Json::from(text);
+            }
+            json!({ "description": description, "encoding": reference.toplevel })
+        })
+        .collect();
+    json!({ "toplevel": toplevel, "fields": fields })
+}
+
+struct Reference {
+    title: String,
+    description: Option<&'static str>,
+    toplevel: Json,
+}
+
+#[derive(Default)]
+struct BinaryDescriber {
+    references: Vec<Reference>,
+}
+
+impl BinaryDescriber {
+    fn toplevel(&mut self, encoding: &Encoding) -> Json {
+        match encoding {
+            Encoding::Tags(tag_size, tags) => self.cases(encoding, *tag_size, tags),
+            Encoding::Option(inner) => self.option(encoding, inner),
+            _ => json!({ "fields": self.fields(encoding) }),
+        }
+    }
+
+    fn fields(&mut self, encoding: &Encoding) -> Vec<Json> {
+        match encoding {
+            Encoding::Obj(_, fields) => {
+                let mut result = Vec::new();
+                for field in fields {
+                    result.extend(self.field(Some(field.get_name()), field.get_encoding()));
+                }
+                result
+            }
+            Encoding::Tup(encodings) => {
+                let mut result = Vec::new();
+                for encoding in encodings {
+                    result.extend(self.field(None, encoding));
+                }
+                result
+            }
+            _ => self.field(None, encoding),
+        }
+    }
+
+    fn field(&mut self, name: Option<&str>, encoding: &Encoding) -> Vec<Json> {
+        match encoding {
+            Encoding::Unit => vec![],
+            Encoding::OptionalField(inner) => {
+                let mut fields = vec![json!({
+                    "kind": "option_indicator",
+                    "name": name.unwrap_or_default(),
+                })];
+                fields.extend(self.field(name, inner));
+                fields
+            }
+            Encoding::String | Encoding::BoundedString(_) => vec![
+                dynamic_size_json(name, "Uint30", 1),
+                field_json(name, json!({ "kind": "String" }), Kind::Variable),
+            ],
+            Encoding::Dynamic(inner) | Encoding::BoundedDynamic(_, inner) => {
+                self.dynamic(name, "Uint30", inner)
+            }
+            Encoding::ShortDynamic(inner) => self.dynamic(name, "Uint8", inner),
+            Encoding::Bounded(_, inner) | Encoding::Greedy(inner) => self.field(name, inner),
+            Encoding::Sized(_, inner)
+                if !matches!(**inner, Encoding::Obj(..) | Encoding::Tup(_)) =>
+            {
+                let layout = self.layout(name, inner);
+                vec![field_json(name, layout, kind(encoding))]
+            }
+            Encoding::Sized(_, inner) => self.field(name, inner),
+            Encoding::Obj(..) | Encoding::Tup(_) if name.is_none() => self.fields(encoding),
+            _ => {
+                let layout = self.layout(name, encoding);
+                vec![field_json(name, layout, kind(encoding))]
+            }
+        }
+    }
+
+    fn dynamic(&mut self, name: Option<&str>, size: &str, inner: &Encoding) -> Vec<Json> {
+        let fields = self.field(name, inner);
+        let mut result = vec![dynamic_size_json(name, size, fields.len())];
+        result.extend(fields);
+        result
+    }
+
+    fn layout(&mut self, name: Option<&str>, encoding: &Encoding) -> Json {
+        match encoding {
+            Encoding::Unit => json!({ "kind": "Zero_width" }),
+            Encoding::Int8 => int_layout("Int8"),
+            Encoding::Uint8 | Encoding::Enum => int_layout("Uint8"),
+            Encoding::Int16 => int_layout("Int16"),
+            Encoding::Uint16 => int_layout("Uint16"),
+            Encoding::Int31 | Encoding::RangedInt => int_layout("Int31"),
+            Encoding::Int32 => int_layout("Int32"),
+            Encoding::Uint32 => int_layout("Uint32"),
+            Encoding::Int64 | Encoding::Timestamp => int_layout("Int64"),
+            Encoding::Bool => json!({ "kind": "Bool" }),
+            Encoding::Float | Encoding::RangedFloat => json!({ "kind": "Float" }),
+            Encoding::String | Encoding::BoundedString(_) => json!({ "kind": "String" }),
+            Encoding::Bytes | Encoding::Hash(_) | Encoding::Signature | Encoding::Custom => {
+                json!({ "kind": "Bytes" })
+            }
+            Encoding::Z => self.number("Z.t", Z_DESCRIPTION),
+            Encoding::Mutez => self.number("N.t", N_DESCRIPTION),
+            Encoding::List(inner) | Encoding::BoundedList(_, inner) => {
+                let mut layout = json!({ "kind": "Seq", "layout": self.layout(name, inner) });
+                if let Encoding::BoundedList(max, _) = encoding {
+                    layout["max_length"] = Json::from(*max);
+                }
+                layout
+            }
+            Encoding::Obj(title, _) => {
+                let toplevel = self.toplevel(encoding);
+                self.reference(title, None, toplevel)
+            }
+            _ => {
+                let toplevel = self.toplevel(encoding);
+                self.reference(name.unwrap_or("anonymous"), None, toplevel)
+            }
+        }
+    }
+
+    fn number(&mut self, title: &str, description: &'static str) -> Json {
+        let toplevel = json!({
+            "fields": [field_json(Some(title), json!({ "kind": "Bytes" }), Kind::Dynamic)]
+        });
+        self.reference(title, Some(description), toplevel)
+    }
+
+    fn cases(&mut self, encoding: &Encoding, tag_size: usize, tags: &TagMap) -> Json {
+        let cases: Vec<Json> = tags
+            .tags()
+            .map(|tag| {
+                let fields = self.fields(tag.get_encoding());
+                self.case(tag_size, tag.get_id(), tag.get_variant(), fields)
+            })
+            .collect();
+        json!({
+            "tag_size": integer_size(tag_size),
+            "kind": kind(encoding).to_json(),
+            "cases": cases,
+        })
+    }
+
+    fn option(&mut self, encoding: &Encoding, inner: &Encoding) -> Json {
+        let fields = self.fields(inner);
+        json!({
+            "tag_size": "Uint8",
+            "kind": kind(encoding).to_json(),
+            "cases": [
+                self.case(1, 0, "None", vec![]),
+                self.case(1, 1, "Some", fields),
+            ],
+        })
+    }
+
+    fn case(&self, tag_size: usize, id: u16, name: &str, fields: Vec<Json>) -> Json {
+        let mut all_fields = vec![field_json(
+            Some("Tag"),
+            int_layout(integer_size(tag_size)),
+            Kind::Fixed(tag_size),
+        )];
+        all_fields.extend(fields);
+        json!({ "tag": id, "fields": all_fields, "name": name })
+    }
+
+    /// Refers to the description `toplevel` under `title`, which is suffixed
+    /// when already used by a different description.
+    fn reference(
+        &mut self,
+        title: &str,
+        description: Option<&'static str>,
+        toplevel: Json,
+    ) -> Json {
+        let mut name = title.to_string();
+        let mut suffix = 0;
+        loop {
+            match self.references.iter().find(|r| r.title == name) {
+                Some(reference) if reference.toplevel == toplevel => break,
+                Some(_) => {
+                    suffix += 1;
+                    name = format!("{}_{}", title, suffix);
+                }
+                None => {
+                    self.references.push(Reference {
+                        title: name.clone(),
+                        description,
+                        toplevel,
+                    });
+                    break;
+                }
+            }
+        }
+        json!({ "kind": "Ref", "name": name })
+    }
+}
+
+/// JSON Schema (draft 04) of the JSON form of `encoding`.
+///
+/// ```rust
+/// use tezos_data_encoding::encoding::{Encoding, Field};
+/// use tezos_data_encoding::schema::json_schema;
+///
+/// let encoding = Encoding::Obj(
+///     "point",
+///     vec![
+///         Field::new("x", Encoding::Int16),
+///         Field::new("label", Encoding::option_field(Encoding::String)),
+///     ],
+/// );
+///
+/// assert_eq!(
+///     json_schema(&encoding),
+///     serde_json::json!({
+///         "$schema": "http://json-schema.org/draft-04/schema#",
+///         "type": "object",
+///         "properties": {
+///             "x": { "type": "integer", "minimum": -32768, "maximum": 32767 },
+///             "label": { "type": "string" }
+///         },
+///         "required": ["x"],
+///         "additionalProperties": false
+///     })
+/// );
+/// ```
+pub fn json_schema(encoding: &Encoding) -> Json {
+    let mut definitions = Map::new();
+    let schema = schema(encoding, &mut definitions);
+
+    let mut result = Map::new();
+    result.insert(
+        "$schema".into(),
+        "http://json-schema.org/draft-04/schema#".into(),
+    );
+    match schema {
+        Json::Object(schema) => result.extend(schema),
+        // All schemas are objects, this is never reached.
+        schema => {
+            result.insert("allOf".into(), json!([schema]));
+        }
+    }
+    if !definitions.is_empty() {
+        result.insert("definitions".into(), Json::Object(definitions));
+    }
+    Json::Object(result)
+}
+
+fn integer_schema(minimum: i64, maximum: i64) -> Json {
+    json!({ "type": "integer", "minimum": minimum, "maximum": maximum })
+}
+
+fn definition(definitions: &mut Map<String, Json>, name: &str, schema: Json) -> Json {
+    definitions.entry(name).or_insert(schema);
+    json!({ "$ref": format!("#/definitions/{}", name) })
+}
+
+fn schema(encoding: &Encoding, definitions: &mut Map<String, Json>) -> Json {
+    match encoding {
+        Encoding::Unit => {
+            json!({ "type": "object", "properties": {}, "additionalProperties": false })
+        }
+        Encoding::Int8 => integer_schema(i8::MIN.into(), i8::MAX.into()),
+        Encoding::Uint8 => integer_schema(u8::MIN.into(), u8::MAX.into()),
+        Encoding::Int16 => integer_schema(i16::MIN.into(), i16::MAX.into()),
+        Encoding::Uint16 => integer_schema(u16::MIN.into(), u16::MAX.into()),
+        Encoding::Int31 => integer_schema(-(1 << 30), (1 << 30) - 1),
+        Encoding::Int32 => integer_schema(i32::MIN.into(), i32::MAX.into()),
+        Encoding::Uint32 => integer_schema(u32::MIN.into(), u32::MAX.into()),
+        Encoding::RangedInt => json!({ "type": "integer" }),
+        Encoding::Int64 => json!({ "type": "string", "pattern": "^-?[0-9]+$" }),
+        Encoding::Z => definition(
+            definitions,
+            "bignum",
+            json!({
+                "title": "Big number",
+                "description": "Decimal representation of a big number",
+                "type": "string",
+                "pattern": "^-?[0-9]+$",
+            }),
+        ),
+        Encoding::Mutez => definition(
+            definitions,
+            "positive_bignum",
+            json!({
+                "title": "Positive big number",
+                "description": "Decimal representation of a positive big number",
+                "type": "string",
+                "pattern": "^[0-9]+$",
+            }),
+        ),
+        Encoding::Float | Encoding::RangedFloat => json!({ "type": "number" }),
+        Encoding::Bool => json!({ "type": "boolean" }),
+        Encoding::String => json!({ "type": "string" }),
+        Encoding::BoundedString(max) => json!({ "type": "string", "maxLength": max }),
+        Encoding::Bytes => json!({ "type": "string", "pattern": "^([a-zA-Z0-9][a-zA-Z0-9])*$" }),
+        Encoding::Hash(hash_type) => json!({
+            "title": format!("{:?} (Base58Check-encoded)", hash_type),
+            "type": "string",
+        }),
+        Encoding::Signature => json!({
+            "title": "A Ed25519, Secp256k1, P256 or BLS signature (Base58Check-encoded)",
+            "type": "string",
+        }),
+        Encoding::Timestamp => json!({
+            "oneOf": [
+                { "type": "string", "format": "date-time" },
+                { "type": "string", "pattern": "^-?[0-9]+$" },
+            ]
+        }),
+        Encoding::Tags(_, tags) => {
+            let cases: Vec<Json> = tags
+                .tags()
+                .map(|tag| {
+                    let variant = tag.get_variant();
+                    match tag.get_encoding() {
+                        Encoding::Unit => {
+                            json!({ "title": variant, "type": "string", "enum": [variant] })
+                        }
+                        encoding => {
+                            let mut case = schema(encoding, definitions);
+                            if let Json::Object(case) = &mut case {
+                                case.entry("title")
+                                    .or_insert_with(|| variant.as_str().into());
+                            }
+                            case
+                        }
+                    }
+                })
+                .collect();
+            json!({ "oneOf": cases })
+        }
+        Encoding::List(inner) => json!({ "type": "array", "items": schema(inner, definitions) }),
+        Encoding::BoundedList(max, inner) => json!({
+            "type": "array",
+            "items": schema(inner, definitions),
+            "maxItems": max,
+        }),
+        Encoding::Enum => integer_schema(0, u8::MAX.into()),
+        Encoding::Option(inner) | Encoding::OptionalField(inner) => json!({
+            "oneOf": [schema(inner, definitions), { "type": "null" }]
+        }),
+        Encoding::Obj(_, fields) => {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+            for field in fields {
+                let name = field.get_name();
+                let field_schema = match field.get_encoding() {
+                    Encoding::OptionalField(inner) => schema(inner, definitions),
+                    encoding => {
+                        required.push(Json::from(name.as_str()));
+                        schema(encoding, definitions)
+                    }
+                };
+                properties.insert(name.clone(), field_schema);
+            }
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false,
+            })
+        }
+        Encoding::Tup(encodings) => match padded_hash(encodings) {
+            Some((hash, _)) => schema(hash, definitions),
+            None => json!({
+                "type": "array",
+                "items": encodings
+                    .iter()
+                    .map(|encoding| schema(encoding, definitions))
+                    .collect::<Vec<_>>(),
+                "additionalItems": false,
+            }),
+        },
+        Encoding::ShortDynamic(inner)
+        | Encoding::Dynamic(inner)
+        | Encoding::BoundedDynamic(_, inner)
+        | Encoding::Sized(_, inner)
+        | Encoding::Bounded(_, inner)
+        | Encoding::Greedy(inner) => schema(inner, definitions),
+        Encoding::Custom => json!({}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{Field, HasEncoding, Tag};
+    use crypto::hash::HashType;
+
+    #[test]
+    fn binary_schema_flattens_fields() {
+        let encoding = Encoding::Obj(
+            "transfer",
+            vec![
+                Field::new("source", Encoding::Hash(HashType::ContractTz1Hash)),
+                Field::new("memo", Encoding::option_field(Encoding::String)),
+                Field::new("data", Encoding::dynamic(Encoding::list(Encoding::Uint8))),
+            ],
+        );
+        assert_eq!(
+            binary_schema(&encoding),
+            json!({
+                "toplevel": {
+                    "fields": [
+                        {
+                            "name": "source",
+                            "layout": { "kind": "Bytes" },
+                            "data_kind": { "size": 20, "kind": "Float" },
+                            "kind": "named"
+                        },
+                        { "kind": "option_indicator", "name": "memo" },
+                        { "kind": "dyn", "num_fields": 1, "size": "Uint30", "name": "memo" },
+                        {
+                            "name": "memo",
+                            "layout": { "kind": "String" },
+                            "data_kind": { "kind": "Variable" },
+                            "kind": "named"
+                        },
+                        { "kind": "dyn", "num_fields": 1, "size": "Uint30", "name": "data" },
+                        {
+                            "name": "data",
+                            "layout": {
+                                "kind": "Seq",
+                                "layout": { "size": "Uint8", "kind": "Int" }
+                            },
+                            "data_kind": { "kind": "Variable" },
+                            "kind": "named"
+                        }
+                    ]
+                },
+                "fields": []
+            })
+        );
+    }
+
+    #[test]
+    fn binary_schema_references() {
+        let point = Encoding::Obj("point", vec![Field::new("x", Encoding::Z)]);
+        let encoding = Encoding::Tags(
+            1,
+            TagMap::new(vec![
+                Tag::new(0, "Origin", Encoding::Unit),
+                Tag::new(1, "Point", point.clone()),
+                Tag::new(2, "Points", Encoding::Tup(vec![point.clone(), point])),
+            ]),
+        );
+        let point_ref = json!({
+            "name": "x",
+            "layout": { "kind": "Ref", "name": "Z.t" },
+            "data_kind": { "kind": "Dynamic" },
+            "kind": "named"
+        });
+        let tag = json!({
+            "name": "Tag",
+            "layout": { "size": "Uint8", "kind": "Int" },
+            "data_kind": { "size": 1, "kind": "Float" },
+            "kind": "named"
+        });
+        let schema = binary_schema(&encoding);
+
+        assert_eq!(schema["toplevel"]["tag_size"], "Uint8");
+        assert_eq!(schema["toplevel"]["kind"], json!({ "kind": "Dynamic" }));
+        let cases = schema["toplevel"]["cases"].as_array().unwrap();
+        assert_eq!(cases.len(), 3);
+        for (id, case) in cases.iter().enumerate() {
+            assert_eq!(case["tag"], id);
+            assert_eq!(case["fields"][0], tag);
+        }
+        assert_eq!(cases[0]["name"], "Origin");
+        assert_eq!(cases[0]["fields"].as_array().unwrap().len(), 1);
+        assert_eq!(cases[1]["fields"][1], point_ref);
+        assert_eq!(cases[2]["fields"][1], point_ref);
+        assert_eq!(cases[2]["fields"][2], point_ref);
+
+        let fields = schema["fields"].as_array().unwrap();
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0]["description"]["title"], "Z.t");
+        assert_eq!(fields[0]["description"]["description"], Z_DESCRIPTION);
+    }
+
+    #[test]
+    fn binary_schema_named_objects() {
+        let inner = |field: &str| Encoding::Obj("inner", vec![Field::new(field, Encoding::Bool)]);
+        let encoding = Encoding::Obj(
+            "outer",
+            vec![
+                Field::new("a", inner("x")),
+                Field::new("b", inner("x")),
+                Field::new("c", inner("y")),
+            ],
+        );
+        let schema = binary_schema(&encoding);
+
+        let names: Vec<_> = schema["toplevel"]["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| field["layout"]["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["inner", "inner", "inner_1"]);
+        let titles: Vec<_> = schema["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| field["description"]["title"].as_str().unwrap())
+            .collect();
+        assert_eq!(titles, ["inner", "inner_1"]);
+        assert_eq!(
+            schema["toplevel"]["fields"][0]["data_kind"],
+            json!({ "size": 1, "kind": "Float" })
+        );
+    }
+
+    #[test]
+    fn json_schema_address() {
+        let schema = json_schema(&crypto::address::Address::encoding());
+        for (case, hash_type) in [(1, "ContractKt1Hash"), (2, "SmartRollupHash")] {
+            assert_eq!(
+                schema["oneOf"][case],
+                json!({
+                    "title": format!("{} (Base58Check-encoded)", hash_type),
+                    "type": "string",
+                }),
+            );
+        }
+    }
+
+    #[test]
+    fn json_schema_definitions() {
+        let encoding = Encoding::Tags(
+            1,
+            TagMap::new(vec![
+                Tag::new(0, "Empty", Encoding::Unit),
+                Tag::new(
+                    1,
+                    "Amount",
+                    Encoding::Obj(
+                        "amount",
+                        vec![
+                            Field::new("value", Encoding::Mutez),
+                            Field::new("values", Encoding::bounded_list(2, Encoding::Mutez)),
+                        ],
+                    ),
+                ),
+            ]),
+        );
+        assert_eq!(
+            json_schema(&encoding),
+            json!({
+                "$schema": "http://json-schema.org/draft-04/schema#",
+                "oneOf": [
+                    { "title": "Empty", "type": "string", "enum": ["Empty"] },
+                    {
+                        "title": "Amount",
+                        "type": "object",
+                        "properties": {
+                            "value": { "$ref": "#/definitions/positive_bignum" },
+                            "values": {
+                                "type": "array",
+                                "items": { "$ref": "#/definitions/positive_bignum" },
+                                "maxItems": 2
+                            }
+                        },
+                        "required": ["value", "values"],
+                        "additionalProperties": false
+                    }
+                ],
+                "definitions": {
+                    "positive_bignum": {
+                        "title": "Positive big number",
+                        "description": "Decimal representation of a positive big number",
+                        "type": "string",
+                        "pattern": "^[0-9]+$"
+                    }
+                }
+            })
+        );
+    }
+}
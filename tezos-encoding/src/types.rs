@@ -3,7 +3,11 @@
 
 //! Defines types of the intermediate data format.
 
-use std::str::FromStr;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::FromStr;
 
 use crate::enc::BinWriter;
 use crate::encoding::Encoding;
@@ -171,14 +175,14 @@ has_encoding!(Mutez, MUTEZ_ENCODING, { Encoding::Mutez });
 //#[cfg_attr(feature = "fuzzing", derive(fuzzcheck::DefaultMutator))]
 pub struct SizedBytes<const SIZE: usize>(pub [u8; SIZE]);
 
-impl<const SIZE: usize> std::fmt::Display for SizedBytes<SIZE> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<const SIZE: usize> core::fmt::Display for SizedBytes<SIZE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", hex::encode(self.0))
     }
 }
 
-impl<const SIZE: usize> std::fmt::Debug for SizedBytes<SIZE> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<const SIZE: usize> core::fmt::Debug for SizedBytes<SIZE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "bytes: {}", hex::encode(self.0))
     }
 }
@@ -225,7 +229,7 @@ struct BytesVisitor<const SIZE: usize>;
 impl<'de, const SIZE: usize> serde::de::Visitor<'de> for BytesVisitor<SIZE> {
     type Value = SizedBytes<{ SIZE }>;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
         formatter.write_str("eigher sequence of bytes or hex encoded data expected")
     }
 
@@ -323,8 +327,17 @@ pub struct Bytes(Vec<u8>);
 
 #[derive(Debug, thiserror::Error)]
 pub enum BytesDecodeError {
-    #[error(transparent)]
-    Hex(#[from] FromHexError),
+    // Without std, `FromHexError` does not implement `Error` and cannot be a source.
+    #[cfg_attr(feature = "std", error(transparent))]
+    #[cfg_attr(not(feature = "std"), error("{0}"))]
+    Hex(#[cfg_attr(feature = "std", from)] FromHexError),
+}
+
+#[cfg(not(feature = "std"))]
+impl From<FromHexError> for BytesDecodeError {
+    fn from(error: FromHexError) -> Self {
+        Self::Hex(error)
+    }
 }
 
 impl Bytes {
@@ -337,14 +350,14 @@ impl Bytes {
     }
 }
 
-impl std::fmt::Debug for Bytes {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for Bytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_tuple("Bytes").field(&self.to_string()).finish()
     }
 }
 
-impl std::fmt::Display for Bytes {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Bytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         hex::encode(&self.0).fmt(f)
     }
 }
@@ -474,7 +487,10 @@ pub const BYTE_FIELD_NONE: u8 = 0;
 /// ```
 ///
 /// After that the intermediate form can be converted to binary by passing it to [crate::binary_writer::BinaryWriter].
-#[derive(PartialEq, Debug)]
+///
+/// Conversely, binary data can be decoded into this form with [crate::binary_reader::BinaryReader],
+/// given its [Encoding].
+#[derive(PartialEq, Debug, Clone)]
 pub enum Value {
     /// Nothing: data is omitted from binary.
     Unit,
@@ -490,10 +506,16 @@ pub enum Value {
     Int31(i32),
     /// Signed 32 bit integer (data is encoded as a 32-bit int in binary and an integer in JSON).
     Int32(i32),
+    /// Unsigned 32 bit integer (data is encoded as a 32-bit int in binary and an integer in JSON).
+    Uint32(u32),
     /// Signed 64 bit integer (data is encoded as a 64-bit int in binary and a decimal string in JSON).
     Int64(i64),
     /// Integer with bounds in a given range. Both bounds are inclusive.
     RangedInt(i32),
+    /// Big number, see [Encoding::Z].
+    Z(num_bigint::BigInt),
+    /// Non-negative big number, see [Encoding::Mutez].
+    Mutez(num_bigint::BigInt),
     /// Encoding of floating point number (encoded as a floating point number in JSON and a double in binary).
     Float(f64),
     /// Float with bounds in a given range. Both bounds are inclusive.